@@ -0,0 +1,3 @@
+//! Package the JavaScript bindings with `wasm-pack build examples/wasm`
+
+pub use fsrsrs::wasm::*;
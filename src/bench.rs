@@ -0,0 +1,89 @@
+//! Export review histories for the open-spaced-repetition benchmark
+//!
+//! The benchmark reads one row per review with the columns `card_id`,
+//! `review_th`, `delta_t`, and `rating`.
+
+use crate::ReviewHistory;
+
+/// One review in the benchmark's format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BenchmarkRow {
+    /// Index of the card's history in the exported histories
+    pub card_id: usize,
+    /// Position of the review among all reviews in time order, from 1
+    pub review_th: usize,
+    /// Whole days since the card's previous review, or 0 for its first
+    pub delta_t: i64,
+    /// Grade of the review, from 1 for Again to 4 for Easy
+    pub rating: u8,
+}
+
+/// Convert review histories to benchmark rows
+///
+/// Rows are ordered by card and then by review. Reviews at the same time
+/// are numbered in card order.
+pub fn to_benchmark_rows(histories: &[ReviewHistory]) -> Vec<BenchmarkRow> {
+    let mut rows = vec![];
+    let mut times = vec![];
+    for (card_id, history) in histories.iter().enumerate() {
+        let mut previous = None;
+        for review in &history.reviews {
+            let delta_t = previous.map_or(0, |previous| {
+                review.when.signed_duration_since(previous).num_days()
+            });
+            previous = Some(review.when);
+            times.push((review.when, rows.len()));
+            rows.push(BenchmarkRow {
+                card_id,
+                review_th: 0,
+                delta_t,
+                rating: review.grade.into(),
+            });
+        }
+    }
+
+    times.sort();
+    for (review_th, (_, row)) in times.into_iter().enumerate() {
+        rows[row].review_th = review_th + 1;
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_benchmark_rows, BenchmarkRow};
+    use crate::{fsrs::tests::string_to_utc, Duration, Grade, Review, ReviewHistory};
+
+    #[test]
+    fn rows() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let at = |days, grade| Review::new(grade, now + Duration::days(days));
+        let histories = [
+            ReviewHistory::new(vec![
+                at(0, Grade::Good),
+                at(3, Grade::Again),
+                at(4, Grade::Good),
+            ]),
+            ReviewHistory::new(vec![at(1, Grade::Easy), at(16, Grade::Hard)]),
+        ];
+
+        let row = |card_id, review_th, delta_t, rating| BenchmarkRow {
+            card_id,
+            review_th,
+            delta_t,
+            rating,
+        };
+        assert_eq!(
+            to_benchmark_rows(&histories),
+            [
+                row(0, 1, 0, 3),
+                row(0, 3, 3, 1),
+                row(0, 4, 1, 3),
+                row(1, 2, 0, 4),
+                row(1, 5, 15, 2),
+            ]
+        );
+        assert!(to_benchmark_rows(&[]).is_empty());
+    }
+}
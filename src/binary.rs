@@ -0,0 +1,216 @@
+use crate::{Card, Duration, Grade};
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Layout of the first version of the binary card format
+#[derive(Serialize, Deserialize)]
+struct CardV1 {
+    reviewed_at: i64,
+    interval: i64,
+    grade: u8,
+    stability: f64,
+    difficulty: f64,
+    lapses: u32,
+}
+
+/// Layout of the second version of the binary card format, which adds the
+/// card's retention
+#[derive(Serialize, Deserialize)]
+struct CardV2 {
+    reviewed_at: i64,
+    interval: i64,
+    grade: u8,
+    stability: f64,
+    difficulty: f64,
+    lapses: u32,
+    retention: Option<f64>,
+}
+
+impl From<CardV1> for CardV2 {
+    fn from(card: CardV1) -> Self {
+        Self {
+            reviewed_at: card.reviewed_at,
+            interval: card.interval,
+            grade: card.grade,
+            stability: card.stability,
+            difficulty: card.difficulty,
+            lapses: card.lapses,
+            retention: None,
+        }
+    }
+}
+
+impl Card {
+    /// Version of the format written by [Card::to_postcard]
+    pub const POSTCARD_VERSION: u8 = 2;
+
+    /// Encode the card compactly with postcard
+    ///
+    /// The first byte is [Card::POSTCARD_VERSION] so that stored cards can
+    /// be migrated if the format changes. Times are stored as whole seconds,
+    /// so any fraction of a second is dropped.
+    pub fn to_postcard(&self) -> Vec<u8> {
+        let wire = CardV2 {
+            reviewed_at: self.reviewed_at.timestamp(),
+            interval: self.interval.num_seconds(),
+            grade: self.grade.into(),
+            stability: self.stability,
+            difficulty: self.difficulty,
+            lapses: self.lapses,
+            retention: self.retention,
+        };
+        postcard::to_extend(&wire, vec![Self::POSTCARD_VERSION])
+            .expect("cards should always serialize")
+    }
+
+    /// Decode a card written by [Card::to_postcard]
+    ///
+    /// Cards written in earlier versions of the format are also accepted.
+    pub fn from_postcard(bytes: &[u8]) -> Result<Card, PostcardError> {
+        let (&version, bytes) = bytes.split_first().ok_or(PostcardError::Empty)?;
+        let wire: CardV2 = match version {
+            1 => postcard::from_bytes::<CardV1>(bytes)
+                .map_err(PostcardError::Decode)?
+                .into(),
+            Self::POSTCARD_VERSION => postcard::from_bytes(bytes).map_err(PostcardError::Decode)?,
+            _ => return Err(PostcardError::Version(version)),
+        };
+        let reviewed_at = Utc
+            .timestamp_opt(wire.reviewed_at, 0)
+            .single()
+            .ok_or(PostcardError::OutOfRange)?;
+        if wire.interval.unsigned_abs() > Duration::max_value().num_seconds().unsigned_abs() {
+            return Err(PostcardError::OutOfRange);
+        }
+        let grade = Grade::try_from(wire.grade).map_err(|_| PostcardError::OutOfRange)?;
+        Ok(Card {
+            reviewed_at,
+            interval: Duration::seconds(wire.interval),
+            grade,
+            stability: wire.stability,
+            difficulty: wire.difficulty,
+            lapses: wire.lapses,
+            retention: wire.retention,
+        })
+    }
+}
+
+/// Failure to decode a card with [Card::from_postcard]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PostcardError {
+    /// There were no bytes to decode
+    Empty,
+    /// The format version is newer than [Card::POSTCARD_VERSION] or unknown
+    Version(u8),
+    /// The bytes after the version are not a card
+    Decode(postcard::Error),
+    /// A time, interval, or grade is outside its valid range
+    OutOfRange,
+}
+
+impl Display for PostcardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PostcardError::Empty => f.write_str("no card data"),
+            PostcardError::Version(version) => write!(
+                f,
+                "unsupported card format version {version}, expected {}",
+                Card::POSTCARD_VERSION
+            ),
+            PostcardError::Decode(error) => write!(f, "invalid card data: {error}"),
+            PostcardError::OutOfRange => f.write_str("card value out of range"),
+        }
+    }
+}
+
+impl Error for PostcardError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PostcardError::Decode(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CardV1, CardV2, PostcardError};
+    use crate::{fsrs::tests::string_to_utc, Card, Duration, Fsrs, Grade};
+
+    #[test]
+    fn round_trip() {
+        let fsrs = Fsrs::default();
+        let mut now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let mut card = None;
+        for grade in [Grade::Good, Grade::Again, Grade::Hard, Grade::Easy] {
+            let next = fsrs.next_card(card, now, grade);
+            let bytes = next.to_postcard();
+            assert_eq!(bytes[0], Card::POSTCARD_VERSION);
+            assert!(bytes.len() < 40);
+            assert_eq!(Card::from_postcard(&bytes), Ok(next));
+            now += next.interval;
+            card = Some(next);
+        }
+
+        let mut custom = card.unwrap();
+        custom.set_retention(Some(0.95)).unwrap();
+        assert_eq!(Card::from_postcard(&custom.to_postcard()), Ok(custom));
+
+        let fraction = Card::from_memory_state(1.0, 5.0, now + Duration::milliseconds(250));
+        let decoded = Card::from_postcard(&fraction.to_postcard()).unwrap();
+        assert_eq!(decoded.reviewed_at, now);
+    }
+
+    #[test]
+    fn version_1() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = Fsrs::default().next_card(None, now, Grade::Good);
+        let wire = CardV1 {
+            reviewed_at: card.reviewed_at.timestamp(),
+            interval: card.interval.num_seconds(),
+            grade: card.grade.into(),
+            stability: card.stability,
+            difficulty: card.difficulty,
+            lapses: card.lapses,
+        };
+        let bytes = postcard::to_extend(&wire, vec![1]).unwrap();
+        assert_eq!(Card::from_postcard(&bytes), Ok(card));
+    }
+
+    #[test]
+    fn errors() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let mut bytes = Card::from_memory_state(1.0, 5.0, now).to_postcard();
+        assert_eq!(Card::from_postcard(&[]), Err(PostcardError::Empty));
+        assert!(matches!(
+            Card::from_postcard(&bytes[..4]),
+            Err(PostcardError::Decode(_))
+        ));
+
+        bytes[0] += 1;
+        let error = Card::from_postcard(&bytes).unwrap_err();
+        assert_eq!(error, PostcardError::Version(3));
+        assert_eq!(
+            error.to_string(),
+            "unsupported card format version 3, expected 2"
+        );
+
+        for interval in [i64::MIN, i64::MAX] {
+            let wire = CardV2 {
+                reviewed_at: now.timestamp(),
+                interval,
+                grade: Grade::Good.into(),
+                stability: 1.0,
+                difficulty: 5.0,
+                lapses: 0,
+                retention: None,
+            };
+            let bytes = postcard::to_extend(&wire, vec![Card::POSTCARD_VERSION]).unwrap();
+            assert_eq!(Card::from_postcard(&bytes), Err(PostcardError::OutOfRange));
+        }
+    }
+}
@@ -1,4 +1,9 @@
-use crate::{Duration, Parameters, Time};
+use crate::{format_interval, Duration, FsrsError, ParamError, Parameters, Time};
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
 
 /// The state of FSRS after a review
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
@@ -7,6 +12,7 @@ pub struct Card {
     /// When the card was last reviewed
     pub reviewed_at: Time,
     /// Time interval from the last review for the next review
+    #[cfg_attr(feature = "serde", serde(with = "interval_seconds"))]
     pub interval: Duration,
     /// Difficulty rating of the review
     pub grade: Grade,
@@ -14,36 +20,668 @@ pub struct Card {
     pub stability: f64,
     /// FSRS memory difficulty after the review
     pub difficulty: f64,
+    /// Number of times the card was forgotten after its first review
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub lapses: u32,
+    /// Desired retention for this card in place of
+    /// [ParametersBuilder::retention](crate::ParametersBuilder::retention)
+    ///
+    /// Set it with [Card::set_retention] to have it checked. Cards produced
+    /// by reviewing this one keep the same retention.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub retention: Option<f64>,
 }
 
 impl Card {
+    /// Smallest stability accepted by [Card::from_memory_state]
+    const MIN_STABILITY: f64 = 0.01;
+
+    /// Create a card with the given memory state, such as one computed by
+    /// another FSRS implementation
+    ///
+    /// Difficulty is clamped to [1, 10] and stability to a small positive
+    /// minimum. The card is due immediately, has no lapses, and uses the
+    /// desired retention of the parameters.
+    pub fn from_memory_state(stability: f64, difficulty: f64, reviewed_at: Time) -> Self {
+        Self {
+            reviewed_at,
+            interval: Duration::zero(),
+            grade: Grade::Good,
+            stability: stability.max(Self::MIN_STABILITY),
+            difficulty: difficulty.clamp(1.0, 10.0),
+            lapses: 0,
+            retention: None,
+        }
+    }
+
+    /// Schedule this card for the given retention instead of the one in the
+    /// parameters, or for the parameters' retention again with None
+    ///
+    /// The interval is unchanged until the next review or
+    /// [Fsrs::reschedule](crate::Fsrs::reschedule).
+    pub fn set_retention(&mut self, retention: Option<f64>) -> Result<(), ParamError> {
+        match retention {
+            Some(r) if !(r > 0.0 && r < 1.0) => Err(ParamError::Retention(r)),
+            _ => {
+                self.retention = retention;
+                Ok(())
+            }
+        }
+    }
+
+    /// Check for a memory state that FSRS could not have produced
+    ///
+    /// See [FsrsError::InvalidState].
+    pub(crate) fn validate(self) -> Result<Self, FsrsError> {
+        let valid_stability = self.stability.is_finite() && self.stability > 0.0;
+        let valid_difficulty = (1.0..=10.0).contains(&self.difficulty);
+        let valid_retention = self.retention.is_none_or(|r| r > 0.0 && r < 1.0);
+        if valid_stability
+            && valid_difficulty
+            && valid_retention
+            && self.interval >= Duration::zero()
+        {
+            Ok(self)
+        } else {
+            Err(FsrsError::InvalidState(self))
+        }
+    }
+
+    /// Reset an invalid stability or difficulty to the initial value for
+    /// the card's grade, returning whether anything was changed
+    ///
+    /// Stability must be finite and positive, and difficulty from 1 to 10,
+    /// as checked by [Fsrs::try_next_card](crate::Fsrs::try_next_card).
+    /// Valid values are left as they are.
+    pub fn sanitize(&mut self, parameters: &Parameters) -> bool {
+        let mut repaired = false;
+        if !(self.stability.is_finite() && self.stability > 0.0) {
+            self.stability = parameters.init_stability(self.grade);
+            repaired = true;
+        }
+        if !(1.0..=10.0).contains(&self.difficulty) {
+            self.difficulty = parameters.init_difficulty(self.grade);
+            repaired = true;
+        }
+        repaired
+    }
+
+    /// The stability and difficulty of the card
+    pub fn memory_state(&self) -> (f64, f64) {
+        (self.stability, self.difficulty)
+    }
+
     /// When the card is due for another review
     pub fn due(&self) -> Time {
         self.reviewed_at + self.interval
     }
 
+    /// Whether the card is due for review at the given time
+    pub fn is_due(&self, now: Time) -> bool {
+        self.due() <= now
+    }
+
+    /// Number of whole days the card is past due, or negative if it is not
+    /// yet due
+    pub fn days_overdue(&self, now: Time) -> i64 {
+        now.signed_duration_since(self.due()).num_days()
+    }
+
+    /// Short label for the interval, such as "3d" or "1.2y"
+    ///
+    /// See [format_interval].
+    pub fn interval_label(&self) -> String {
+        format_interval(self.interval)
+    }
+
     /// Amount of time passed since the last review
     pub fn elapsed(&self, now: Time) -> Duration {
         now.signed_duration_since(self.reviewed_at)
     }
 
-    /// Amount of time in days since the last review
+    /// Amount of time in whole days since the last review
+    ///
+    /// Times before the last review count as zero days.
     pub fn elapsed_days(&self, now: Time) -> i64 {
-        self.elapsed(now).num_days()
+        self.elapsed(now).num_days().max(0)
     }
 
     /// FSRS memory retrievability after the review
+    ///
+    /// Studying before the last review uses zero elapsed days, so the
+    /// retrievability is 1.
     pub fn retrievability(&self, parameters: &Parameters, now: Time) -> f64 {
         parameters.forgetting_curve(self.elapsed_days(now) as f64, self.stability)
     }
+
+    /// Probability of recalling the card at the given time, for reporting
+    ///
+    /// This is currently the same as [Card::retrievability]. Prefer it for
+    /// analytics, since it may later account for more than the forgetting
+    /// curve, such as the card's difficulty.
+    pub fn predicted_recall(&self, parameters: &Parameters, now: Time) -> f64 {
+        self.retrievability(parameters, now)
+    }
+
+    /// Days from the last review until retrievability falls to the target
+    ///
+    /// Returns 0 for targets of 1 or more, since retrievability starts at 1,
+    /// and infinity for targets of 0 or less. Unlike scheduled intervals,
+    /// the result is not rounded or limited by the maximum interval.
+    pub fn days_to_retrievability(&self, parameters: &Parameters, target: f64) -> f64 {
+        if target >= 1.0 {
+            0.0
+        } else if target <= 0.0 {
+            f64::INFINITY
+        } else {
+            parameters.inverse_forgetting_curve(self.stability, target)
+        }
+    }
+
+    /// Whether the card has lapsed at least as many times as the leech
+    /// threshold
+    ///
+    /// Always false when the threshold is zero.
+    pub fn is_leech(&self, parameters: &Parameters) -> bool {
+        parameters.leech_threshold > 0 && self.lapses >= parameters.leech_threshold
+    }
+
+    /// Whether the review that produced this card made it a leech
+    ///
+    /// True only for the lapse that reached the leech threshold, so that it
+    /// can be flagged once rather than on every later review.
+    pub fn became_leech(&self, parameters: &Parameters) -> bool {
+        self.grade == Grade::Again
+            && parameters.leech_threshold > 0
+            && self.lapses == parameters.leech_threshold
+    }
+}
+
+#[cfg(feature = "chrono-tz")]
+impl Card {
+    /// When the card is due in the given time zone
+    pub fn next_review_local(&self, tz: chrono_tz::Tz) -> chrono::DateTime<chrono_tz::Tz> {
+        self.due().with_timezone(&tz)
+    }
+
+    /// The start of the local day when the card is due
+    ///
+    /// For intervals of at least a day, the due time moves back to local
+    /// midnight so that the card is due for the whole day, however daylight
+    /// saving time has shifted since the review. Where midnight is skipped,
+    /// the day starts an hour later. Shorter intervals keep their exact due
+    /// time. To have study days start at another hour, see
+    /// [ParametersBuilder::day_cutoff](crate::ParametersBuilder::day_cutoff).
+    pub fn next_review_local_day(&self, tz: chrono_tz::Tz) -> chrono::DateTime<chrono_tz::Tz> {
+        use chrono::{NaiveTime, TimeZone};
+
+        let due = self.next_review_local(tz);
+        if self.interval < Duration::days(1) {
+            return due;
+        }
+        let midnight = due.date_naive().and_time(NaiveTime::MIN);
+        tz.from_local_datetime(&midnight)
+            .earliest()
+            .or_else(|| {
+                tz.from_local_datetime(&(midnight + Duration::hours(1)))
+                    .earliest()
+            })
+            .unwrap_or(due)
+    }
 }
 
 /// Difficulty classification of a review
+///
+/// Grades are numbered from 1 for Again to 4 for Easy, as in other FSRS
+/// implementations. In human-readable formats, grades serialize as their
+/// lowercase names and deserialize from either their names or numbers.
+/// Other formats use the numbers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Grade {
     Again = 1,
     Hard = 2,
     Good = 3,
     Easy = 4,
 }
+
+impl Grade {
+    /// Every grade from hardest to easiest
+    pub const fn all() -> [Grade; 4] {
+        [Grade::Again, Grade::Hard, Grade::Good, Grade::Easy]
+    }
+
+    /// Position of the grade in [Grade::all], from 0 to 3
+    pub const fn index(self) -> u8 {
+        self as u8 - 1
+    }
+
+    /// The grade at the given position in [Grade::all]
+    pub const fn from_index(index: u8) -> Option<Grade> {
+        match index {
+            0 => Some(Grade::Again),
+            1 => Some(Grade::Hard),
+            2 => Some(Grade::Good),
+            3 => Some(Grade::Easy),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<u8> for Grade {
+    type Error = GradeValueError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        value
+            .checked_sub(1)
+            .and_then(Grade::from_index)
+            .ok_or(GradeValueError(value))
+    }
+}
+
+impl From<Grade> for u8 {
+    fn from(grade: Grade) -> Self {
+        grade as u8
+    }
+}
+
+impl Display for Grade {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Grade::Again => "again",
+            Grade::Hard => "hard",
+            Grade::Good => "good",
+            Grade::Easy => "easy",
+        })
+    }
+}
+
+impl FromStr for Grade {
+    type Err = ParseGradeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grade::all()
+            .into_iter()
+            .find(|grade| grade.to_string() == s)
+            .ok_or_else(|| ParseGradeError(s.to_string()))
+    }
+}
+
+/// A string that is not the lowercase name of a [Grade]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ParseGradeError(pub String);
+
+impl Display for ParseGradeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown grade {:?}, expected again, hard, good, or easy",
+            self.0
+        )
+    }
+}
+
+impl Error for ParseGradeError {}
+
+/// A number that is not from 1 for [Grade::Again] to 4 for [Grade::Easy]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GradeValueError(pub u8);
+
+impl Display for GradeValueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "grade {} is not from 1 to 4", self.0)
+    }
+}
+
+impl Error for GradeValueError {}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Grade {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_u8(u8::from(*self))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Grade {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct GradeVisitor;
+
+        impl serde::de::Visitor<'_> for GradeVisitor {
+            type Value = Grade;
+
+            fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+                f.write_str("a grade name or a number from 1 to 4")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, s: &str) -> Result<Grade, E> {
+                s.parse().map_err(E::custom)
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Grade, E> {
+                u8::try_from(value)
+                    .ok()
+                    .and_then(|value| Grade::try_from(value).ok())
+                    .ok_or_else(|| E::custom(format!("grade {value} is not from 1 to 4")))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Grade, E> {
+                u64::try_from(value)
+                    .map_err(|_| E::custom(format!("grade {value} is not from 1 to 4")))
+                    .and_then(|value| self.visit_u64(value))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(GradeVisitor)
+        } else {
+            deserializer.deserialize_u8(GradeVisitor)
+        }
+    }
+}
+
+/// Serializes the review interval as a whole number of seconds
+#[cfg(feature = "serde")]
+mod interval_seconds {
+    use crate::Duration;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(interval: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        interval.num_seconds().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let seconds = i64::deserialize(deserializer)?;
+        if seconds.unsigned_abs() > Duration::max_value().num_seconds().unsigned_abs() {
+            return Err(serde::de::Error::custom("review interval out of range"));
+        }
+        Ok(Duration::seconds(seconds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GradeValueError, ParseGradeError};
+    use crate::{
+        fsrs::tests::string_to_utc, Card, Duration, Fsrs, Grade, Parameters, ParametersBuilder,
+        Time,
+    };
+
+    fn card_due_in(days: i64) -> (Card, Time) {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = Card {
+            reviewed_at: now - Duration::days(10),
+            interval: Duration::days(10 + days),
+            grade: Grade::Good,
+            stability: 10.0,
+            difficulty: 5.0,
+            lapses: 0,
+            retention: None,
+        };
+        (card, now)
+    }
+
+    #[test]
+    fn predicted_recall() {
+        let parameters = Parameters::default();
+        let (card, now) = card_due_in(0);
+        for days in [-5, 0, 1, 10, 100] {
+            let then = now + Duration::days(days);
+            assert_eq!(
+                card.predicted_recall(&parameters, then),
+                card.retrievability(&parameters, then)
+            );
+        }
+    }
+
+    #[test]
+    fn sanitize() {
+        let parameters = Parameters::default();
+        let (card, _) = card_due_in(3);
+        let mut repaired = card;
+        assert!(!repaired.sanitize(&parameters));
+        assert_eq!(repaired, card);
+
+        repaired.stability = f64::NAN;
+        assert!(repaired.sanitize(&parameters));
+        assert!(repaired.stability.is_finite());
+        assert_eq!(repaired.stability, parameters.init_stability(card.grade));
+        assert_eq!(repaired.difficulty, card.difficulty);
+
+        repaired.difficulty = f64::INFINITY;
+        repaired.stability = -1.0;
+        assert!(repaired.sanitize(&parameters));
+        assert_eq!(repaired.difficulty, parameters.init_difficulty(card.grade));
+        assert!(repaired.validate().is_ok());
+    }
+
+    #[test]
+    fn due_yesterday() {
+        let (card, now) = card_due_in(-1);
+        assert!(card.is_due(now));
+        assert_eq!(card.days_overdue(now), 1);
+    }
+
+    #[test]
+    fn due_now() {
+        let (card, now) = card_due_in(0);
+        assert!(card.is_due(now));
+        assert_eq!(card.days_overdue(now), 0);
+    }
+
+    #[test]
+    fn due_next_week() {
+        let (card, now) = card_due_in(7);
+        assert!(!card.is_due(now));
+        assert_eq!(card.days_overdue(now), -7);
+        assert_eq!(card.days_overdue(now + Duration::hours(12)), -6);
+    }
+
+    #[test]
+    fn reviewed_before_last_review() {
+        let fsrs = Fsrs::default();
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = fsrs.next_card(None, now, Grade::Good);
+        let early = now - Duration::days(1);
+        assert_eq!(card.elapsed_days(early), 0);
+        assert_eq!(card.retrievability(fsrs.parameters(), early), 1.0);
+
+        let next = fsrs.next_card(Some(card), early, Grade::Good);
+        assert_eq!(
+            next.stability,
+            fsrs.next_card(Some(card), now, Grade::Good).stability
+        );
+    }
+
+    #[test]
+    fn grade_index() {
+        for (i, grade) in Grade::all().into_iter().enumerate() {
+            assert_eq!(grade.index() as usize, i);
+            assert_eq!(Grade::from_index(i as u8), Some(grade));
+        }
+        assert_eq!(Grade::from_index(4), None);
+        assert!(Grade::all().windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn grade_numbers() {
+        for (value, grade) in (1..=4).zip(Grade::all()) {
+            assert_eq!(Grade::try_from(value), Ok(grade));
+            assert_eq!(u8::from(grade), value);
+        }
+        assert_eq!(Grade::try_from(0), Err(GradeValueError(0)));
+        assert_eq!(Grade::try_from(5), Err(GradeValueError(5)));
+        assert_eq!(Grade::try_from(255), Err(GradeValueError(255)));
+        assert_eq!(GradeValueError(0).to_string(), "grade 0 is not from 1 to 4");
+    }
+
+    #[test]
+    fn grade_names() {
+        for grade in Grade::all() {
+            assert_eq!(grade.to_string().parse(), Ok(grade));
+        }
+        assert_eq!(Grade::Again.to_string(), "again");
+        assert_eq!(
+            "Good".parse::<Grade>(),
+            Err(ParseGradeError("Good".to_string()))
+        );
+        assert!("".parse::<Grade>().is_err());
+    }
+
+    #[test]
+    fn memory_state() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = Card::from_memory_state(12.5, 6.25, now);
+        assert_eq!(card.memory_state(), (12.5, 6.25));
+        assert!(card.is_due(now));
+
+        let clamped = Card::from_memory_state(-3.0, 42.0, now);
+        assert_eq!(clamped.memory_state(), (0.01, 10.0));
+        assert_eq!(Card::from_memory_state(1.0, 0.0, now).difficulty, 1.0);
+    }
+
+    #[test]
+    fn days_to_retrievability() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let parameters = Parameters::default();
+        let card = Card::from_memory_state(12.0, 5.0, now);
+        let days = card.days_to_retrievability(&parameters, 0.9);
+        assert!((days - 12.0).abs() < 1e-9);
+
+        let days = card.days_to_retrievability(&parameters, 0.8);
+        assert!(days > 12.0);
+        assert!((parameters.forgetting_curve(days, 12.0) - 0.8).abs() < 1e-12);
+
+        assert_eq!(card.days_to_retrievability(&parameters, 1.0), 0.0);
+        assert_eq!(card.days_to_retrievability(&parameters, 1.5), 0.0);
+        assert_eq!(card.days_to_retrievability(&parameters, 0.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn leech() {
+        let parameters = ParametersBuilder::new().leech_threshold(3).build();
+        let fsrs = Fsrs::new(parameters);
+        let mut now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let mut card = fsrs.next_card(None, now, Grade::Again);
+        assert_eq!(card.lapses, 0);
+
+        let mut crossings = 0;
+        for lapses in 1..=5 {
+            now += card.interval;
+            card = fsrs.next_card(Some(card), now, Grade::Good);
+            now += card.interval;
+            card = fsrs.next_card(Some(card), now, Grade::Again);
+            assert_eq!(card.lapses, lapses);
+            assert_eq!(card.is_leech(&parameters), lapses >= 3);
+            crossings += card.became_leech(&parameters) as u32;
+        }
+        assert_eq!(crossings, 1);
+
+        let disabled = ParametersBuilder::new().leech_threshold(0).build();
+        assert!(!card.is_leech(&disabled));
+        assert!(!card.became_leech(&disabled));
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn local_due_dates() {
+        use chrono::{TimeZone, Timelike};
+        use chrono_tz::{America::New_York, America::Santiago};
+
+        // Daylight saving time starts in New York on 2023-03-12
+        let reviewed_at = New_York
+            .with_ymd_and_hms(2023, 3, 5, 9, 0, 0)
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let card = Card {
+            interval: Duration::days(10),
+            ..Card::from_memory_state(10.0, 5.0, reviewed_at)
+        };
+        let due = card.next_review_local(New_York);
+        assert_eq!(due, card.due());
+        assert_eq!(due.hour(), 10);
+        assert_eq!(
+            card.next_review_local_day(New_York),
+            New_York.with_ymd_and_hms(2023, 3, 15, 0, 0, 0).unwrap()
+        );
+
+        let soon = Card {
+            interval: Duration::minutes(10),
+            ..card
+        };
+        assert_eq!(
+            soon.next_review_local_day(New_York),
+            soon.next_review_local(New_York)
+        );
+
+        // Daylight saving time starts at midnight in Santiago on 2023-09-03
+        let reviewed_at = Santiago
+            .with_ymd_and_hms(2023, 9, 1, 12, 0, 0)
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let card = Card {
+            interval: Duration::days(2),
+            ..Card::from_memory_state(2.0, 5.0, reviewed_at)
+        };
+        let day = card.next_review_local_day(Santiago);
+        assert_eq!(
+            day.date_naive(),
+            card.next_review_local(Santiago).date_naive()
+        );
+        assert_eq!(day.hour(), 1);
+        assert!(day <= card.due());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_grade() {
+        for (value, grade) in (1..=4).zip(Grade::all()) {
+            assert_eq!(serde_json::to_value(grade).unwrap(), grade.to_string());
+            assert_eq!(
+                serde_json::from_value::<Grade>(value.into()).unwrap(),
+                grade
+            );
+            let name = serde_json::Value::from(grade.to_string());
+            assert_eq!(serde_json::from_value::<Grade>(name).unwrap(), grade);
+        }
+        for invalid in ["0", "5", "-1", "300", "\"Good\"", "3.0"] {
+            assert!(serde_json::from_str::<Grade>(invalid).is_err());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let fsrs = Fsrs::default();
+        let mut now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let mut card = None;
+        for grade in [Grade::Good, Grade::Again, Grade::Hard, Grade::Easy] {
+            let next = fsrs.next_card(card, now, grade);
+            let json = serde_json::to_string(&next).unwrap();
+            let parsed = serde_json::from_str(&json).unwrap();
+            assert_eq!(next, parsed);
+            now += next.interval;
+            card = Some(next);
+        }
+
+        let json = serde_json::to_value(card.unwrap()).unwrap();
+        assert_eq!(json["grade"], "easy");
+        assert_eq!(json["reviewed_at"], "2022-12-04T12:30:00Z");
+        assert_eq!(json["lapses"], 1);
+
+        // Cards serialized before lapses were tracked have none
+        let mut json = json;
+        json.as_object_mut().unwrap().remove("lapses");
+        let parsed: Card = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(parsed.lapses, 0);
+
+        for seconds in [i64::MIN, i64::MAX] {
+            let mut json = json.clone();
+            json["interval"] = seconds.into();
+            assert!(serde_json::from_value::<Card>(json).is_err());
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{Card, Duration, Fsrs, FsrsError, Grade, Time};
+
+/// Builder for [`Card`], such as for importing cards from elsewhere
+///
+/// Unset fields take the values of a card first reviewed as
+/// [Grade::Good] with the default [Parameters](crate::Parameters).
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct CardBuilder {
+    reviewed_at: Time,
+    due: Option<Time>,
+    interval: Option<Duration>,
+    grade: Option<Grade>,
+    stability: Option<f64>,
+    difficulty: Option<f64>,
+    lapses: Option<u32>,
+    retention: Option<f64>,
+}
+
+impl CardBuilder {
+    /// Create a new builder for a card last reviewed at the given time
+    pub fn new(reviewed_at: Time) -> Self {
+        Self {
+            reviewed_at,
+            due: None,
+            interval: None,
+            grade: None,
+            stability: None,
+            difficulty: None,
+            lapses: None,
+            retention: None,
+        }
+    }
+
+    /// Build the [Card], clamping the memory state like
+    /// [Card::from_memory_state] and negative intervals to zero
+    ///
+    /// A retention outside (0, 1) is dropped in favour of the parameters'.
+    pub fn build(self) -> Card {
+        let card = self.unchecked();
+        Card {
+            interval: card.interval.max(Duration::zero()),
+            grade: card.grade,
+            lapses: card.lapses,
+            retention: card.retention.filter(|r| *r > 0.0 && *r < 1.0),
+            ..Card::from_memory_state(card.stability, card.difficulty, card.reviewed_at)
+        }
+    }
+
+    /// Build the [Card], rejecting memory states that FSRS could not have
+    /// produced and retentions outside (0, 1) with [FsrsError::InvalidState]
+    pub fn try_build(self) -> Result<Card, FsrsError> {
+        self.unchecked().validate()
+    }
+
+    fn unchecked(self) -> Card {
+        let first = Fsrs::default().next_card(None, self.reviewed_at, Grade::Good);
+        let interval = match (self.due, self.interval) {
+            (Some(due), _) => due.signed_duration_since(self.reviewed_at),
+            (None, Some(interval)) => interval,
+            (None, None) => first.interval,
+        };
+        Card {
+            reviewed_at: self.reviewed_at,
+            interval,
+            grade: self.grade.unwrap_or(first.grade),
+            stability: self.stability.unwrap_or(first.stability),
+            difficulty: self.difficulty.unwrap_or(first.difficulty),
+            lapses: self.lapses.unwrap_or(first.lapses),
+            retention: self.retention,
+        }
+    }
+
+    /// Set when the card is due, replacing any interval
+    pub fn due(mut self, due: Time) -> Self {
+        self.due = Some(due);
+        self.interval = None;
+        self
+    }
+
+    /// Set the time from the last review until the card is due, replacing
+    /// any due time
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self.due = None;
+        self
+    }
+
+    /// Set the grade of the last review
+    pub fn grade(mut self, grade: Grade) -> Self {
+        self.grade = Some(grade);
+        self
+    }
+
+    /// Set the memory stability in days
+    pub fn stability(mut self, stability: f64) -> Self {
+        self.stability = Some(stability);
+        self
+    }
+
+    /// Set the memory difficulty, from 1 to 10
+    pub fn difficulty(mut self, difficulty: f64) -> Self {
+        self.difficulty = Some(difficulty);
+        self
+    }
+
+    /// Set the number of lapses
+    pub fn lapses(mut self, lapses: u32) -> Self {
+        self.lapses = Some(lapses);
+        self
+    }
+
+    /// Set the desired retention for this card in place of the parameters'
+    ///
+    /// See [Card::retention].
+    pub fn retention(mut self, retention: f64) -> Self {
+        self.retention = Some(retention);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CardBuilder;
+    use crate::{fsrs::tests::string_to_utc, Duration, Fsrs, FsrsError, Grade};
+
+    #[test]
+    fn build() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let fsrs = Fsrs::default();
+        assert_eq!(
+            CardBuilder::new(now).build(),
+            fsrs.next_card(None, now, Grade::Good)
+        );
+
+        let card = CardBuilder::new(now - Duration::days(20))
+            .stability(30.0)
+            .difficulty(4.0)
+            .due(now)
+            .lapses(2)
+            .grade(Grade::Hard)
+            .try_build()
+            .unwrap();
+        assert_eq!(card.interval, Duration::days(20));
+        assert_eq!(card.memory_state(), (30.0, 4.0));
+        assert_eq!(card.lapses, 2);
+        assert_eq!(card.grade, Grade::Hard);
+        assert!(card.is_due(now));
+
+        let next = fsrs.next_card(Some(card), now, Grade::Good);
+        assert!(next.stability > card.stability);
+        assert_eq!(next.lapses, 2);
+        let next = fsrs.next_card(Some(card), now, Grade::Again);
+        assert_eq!(next.lapses, 3);
+    }
+
+    #[test]
+    fn bounds() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let builder = CardBuilder::new(now).stability(-1.0).difficulty(11.0);
+        assert!(matches!(
+            builder.try_build(),
+            Err(FsrsError::InvalidState(_))
+        ));
+        let card = builder.interval(Duration::days(-5)).lapses(1).build();
+        assert_eq!(card.memory_state(), (0.01, 10.0));
+        assert_eq!(card.interval, Duration::zero());
+        assert_eq!(card.lapses, 1);
+
+        let strict = CardBuilder::new(now).retention(1.5);
+        assert!(strict.try_build().is_err());
+        assert_eq!(strict.build().retention, None);
+        let strict = CardBuilder::new(now).retention(0.95);
+        assert_eq!(strict.try_build().unwrap().retention, Some(0.95));
+
+        let late = CardBuilder::new(now).due(now - Duration::days(1));
+        assert!(late.try_build().is_err());
+        assert!(late.interval(Duration::days(3)).try_build().is_ok());
+    }
+}
@@ -1,7 +1,9 @@
 use crate::Grade::{self, *};
 use std::ops::{Index, IndexMut};
 
+/// A value for each of the four review grades
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cards<T: Copy> {
     pub again: T,
     pub hard: T,
@@ -10,6 +12,7 @@ pub struct Cards<T: Copy> {
 }
 
 impl<T: Copy> Cards<T> {
+    /// Create a new [Cards] from the value for each grade
     pub fn new(again: T, hard: T, good: T, easy: T) -> Self {
         Self {
             again,
@@ -19,22 +22,27 @@ impl<T: Copy> Cards<T> {
         }
     }
 
+    /// Use the same value for every grade
     pub fn splat(t: T) -> Self {
         Self::new(t, t, t, t)
     }
 
+    /// Compute the value for each grade
     pub fn from_fn(f: impl Fn(Grade) -> T) -> Self {
         Self::new(f(Again), f(Hard), f(Good), f(Easy))
     }
 
+    /// The values in grade order
     pub fn into_array(self) -> [T; 4] {
         [self.again, self.hard, self.good, self.easy]
     }
 
+    /// References to the values in grade order
     pub fn as_array(&self) -> [&T; 4] {
         [&self.again, &self.hard, &self.good, &self.easy]
     }
 
+    /// Mutable references to the values in grade order
     pub fn as_array_mut(&mut self) -> [&mut T; 4] {
         [
             &mut self.again,
@@ -44,6 +52,7 @@ impl<T: Copy> Cards<T> {
         ]
     }
 
+    /// Transform the value for each grade
     pub fn map<R: Copy>(self, f: impl Fn((Grade, T)) -> R) -> Cards<R> {
         Cards::new(
             f((Again, self.again)),
@@ -53,6 +62,7 @@ impl<T: Copy> Cards<T> {
         )
     }
 
+    /// Modify the value for each grade in place
     pub fn update(&mut self, f: impl Fn((Grade, &mut T))) {
         f((Again, &mut self.again));
         f((Hard, &mut self.hard));
@@ -118,3 +128,23 @@ impl<'a, T: Copy> IntoIterator for &'a mut Cards<T> {
         self.as_array_mut().into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use crate::{fsrs::tests::string_to_utc, Card, Cards, Fsrs, Grade};
+
+        let fsrs = Fsrs::default();
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = fsrs.next_card(None, now, Grade::Good);
+        let preview = fsrs.preview(Some(card), now + card.interval);
+
+        let json = serde_json::to_value(preview).unwrap();
+        assert_eq!(json["again"]["grade"], "again");
+        assert_eq!(json["easy"]["grade"], "easy");
+        let parsed: Cards<Card> = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, preview);
+    }
+}
@@ -0,0 +1,23 @@
+use crate::Time;
+
+/// Source of the current time
+pub trait Clock {
+    /// The current time
+    fn now(&self) -> Time;
+}
+
+/// [Clock] that reads the system time
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Time {
+        chrono::Utc::now()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Time {
+        (**self).now()
+    }
+}
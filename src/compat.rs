@@ -0,0 +1,105 @@
+//! Importers for data exported by other FSRS implementations
+
+use crate::ReviewLog;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    io::{self, BufRead},
+};
+
+pub mod pyfsrs;
+pub mod tsfsrs;
+
+/// Failure to import data from another FSRS implementation
+#[derive(Debug)]
+pub enum ImportError {
+    /// The input is not valid JSON of the expected shape
+    Json(serde_json::Error),
+    /// The card state is not one of New, Learning, Review, or Relearning
+    State(String),
+    /// A field needed for the import is missing or null
+    MissingField(&'static str),
+    /// The rating is not from 1 for Again to 4 for Easy
+    Rating(i64),
+    /// The timestamp in milliseconds is out of range
+    Timestamp(i64),
+    /// The input could not be read
+    Io(io::Error),
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Json(error) => write!(f, "invalid JSON: {error}"),
+            ImportError::State(state) => write!(f, "unknown card state {state:?}"),
+            ImportError::MissingField(field) => write!(f, "missing field {field}"),
+            ImportError::Rating(rating) => write!(f, "rating {rating} is not 1 to 4"),
+            ImportError::Timestamp(ms) => write!(f, "timestamp {ms} is out of range"),
+            ImportError::Io(error) => write!(f, "failed to read input: {error}"),
+        }
+    }
+}
+
+impl Error for ImportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ImportError::Json(error) => Some(error),
+            ImportError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for ImportError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+impl From<io::Error> for ImportError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Read review logs from JSON lines, one [ReviewLog] per line
+///
+/// Lines are read and parsed as the iterator advances, so large files need
+/// not fit in memory. A line that fails to parse produces an error without
+/// ending the iteration. Blank lines are skipped.
+pub fn import_jsonl<R: BufRead>(reader: R) -> impl Iterator<Item = Result<ReviewLog, ImportError>> {
+    reader.lines().filter_map(|line| match line {
+        Ok(line) if line.trim().is_empty() => None,
+        Ok(line) => Some(serde_json::from_str(&line).map_err(ImportError::from)),
+        Err(error) => Some(Err(error.into())),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{import_jsonl, ImportError};
+    use crate::{fsrs::tests::string_to_utc, Fsrs, Grade, ReviewLog};
+
+    #[test]
+    fn jsonl() {
+        let fsrs = Fsrs::default();
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let first = fsrs.next_card(None, now, Grade::Good);
+        let second = fsrs.next_card(Some(first), first.due(), Grade::Hard);
+        let logs = [
+            ReviewLog::new(None, first),
+            ReviewLog::new(Some(first), second),
+        ];
+
+        let input = format!(
+            "{}\n{{\"grade\": \"good\"}}\n\n{}\n",
+            serde_json::to_string(&logs[0]).unwrap(),
+            serde_json::to_string(&logs[1]).unwrap(),
+        );
+        let results: Vec<_> = import_jsonl(input.as_bytes()).collect();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap(), &logs[0]);
+        assert!(matches!(results[1], Err(ImportError::Json(_))));
+        assert_eq!(results[2].as_ref().unwrap(), &logs[1]);
+    }
+}
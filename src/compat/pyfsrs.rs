@@ -0,0 +1,182 @@
+//! Import from the Python `fsrs` package
+
+use super::ImportError;
+use crate::{Card, Duration, Grade, Time};
+use serde::Deserialize;
+
+/// A card as serialized by `Card.to_dict` in py-fsrs
+#[derive(Deserialize)]
+struct PyCard {
+    due: Time,
+    stability: Option<f64>,
+    difficulty: Option<f64>,
+    scheduled_days: Option<i64>,
+    #[serde(default)]
+    lapses: u32,
+    state: PyState,
+    last_review: Option<Time>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PyState {
+    Index(u8),
+    Name(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    New,
+    Learning,
+    Review,
+    Relearning,
+}
+
+impl TryFrom<PyState> for State {
+    type Error = ImportError;
+
+    fn try_from(state: PyState) -> Result<Self, Self::Error> {
+        match state {
+            PyState::Index(0) => Ok(State::New),
+            PyState::Index(1) => Ok(State::Learning),
+            PyState::Index(2) => Ok(State::Review),
+            PyState::Index(3) => Ok(State::Relearning),
+            PyState::Index(index) => Err(ImportError::State(index.to_string())),
+            PyState::Name(name) => {
+                let normalized = name
+                    .trim()
+                    .trim_start_matches("State.")
+                    .to_ascii_lowercase();
+                match normalized.as_str() {
+                    "new" => Ok(State::New),
+                    "learning" => Ok(State::Learning),
+                    "review" | "reviewing" => Ok(State::Review),
+                    "relearning" => Ok(State::Relearning),
+                    _ => Err(ImportError::State(name)),
+                }
+            }
+        }
+    }
+}
+
+/// Convert a card from the JSON produced by py-fsrs
+///
+/// Both the numeric and named forms of the card state are accepted. New
+/// cards have no memory state and produce None. The interval runs from
+/// `last_review` to `due`; if `last_review` is missing it is inferred from
+/// `scheduled_days`. The review count is not tracked by [Card] and is
+/// ignored. Since py-fsrs does not record the last rating, relearning
+/// cards are given [Grade::Again] and others [Grade::Good].
+pub fn from_pyfsrs_card(json: &str) -> Result<Option<Card>, ImportError> {
+    let card: PyCard = serde_json::from_str(json)?;
+    let state = State::try_from(card.state)?;
+    if state == State::New || (card.stability.is_none() && card.last_review.is_none()) {
+        return Ok(None);
+    }
+
+    let reviewed_at = match (card.last_review, card.scheduled_days) {
+        (Some(last_review), _) => last_review,
+        (None, Some(days)) => card.due - Duration::days(days),
+        (None, None) => return Err(ImportError::MissingField("last_review")),
+    };
+    let stability = card
+        .stability
+        .ok_or(ImportError::MissingField("stability"))?;
+    let difficulty = card
+        .difficulty
+        .ok_or(ImportError::MissingField("difficulty"))?;
+    let grade = match state {
+        State::Relearning => Grade::Again,
+        _ => Grade::Good,
+    };
+
+    Ok(Some(Card {
+        interval: card.due.signed_duration_since(reviewed_at),
+        grade,
+        lapses: card.lapses,
+        ..Card::from_memory_state(stability, difficulty, reviewed_at)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_pyfsrs_card;
+    use crate::{compat::ImportError, fsrs::tests::string_to_utc, Duration, Grade};
+
+    const REVIEW_CARD: &str = r#"{
+        "due": "2022-12-09T12:30:00.359549+00:00",
+        "stability": 8.2956,
+        "difficulty": 6.4133,
+        "elapsed_days": 3,
+        "scheduled_days": 10,
+        "reps": 4,
+        "lapses": 1,
+        "state": 2,
+        "last_review": "2022-11-29T12:30:00.359549+00:00"
+    }"#;
+
+    #[test]
+    fn review_card() {
+        let card = from_pyfsrs_card(REVIEW_CARD).unwrap().unwrap();
+        let reviewed_at =
+            string_to_utc("2022-11-29 12:30:00 +0000 UTC") + Duration::microseconds(359549);
+        assert_eq!(card.reviewed_at, reviewed_at);
+        assert_eq!(card.interval, Duration::days(10));
+        assert_eq!(card.memory_state(), (8.2956, 6.4133));
+        assert_eq!(card.grade, Grade::Good);
+        assert_eq!(card.lapses, 1);
+    }
+
+    #[test]
+    fn state_names() {
+        let named = REVIEW_CARD.replace(r#""state": 2"#, r#""state": "Reviewing""#);
+        assert_eq!(
+            from_pyfsrs_card(&named).unwrap(),
+            from_pyfsrs_card(REVIEW_CARD).unwrap()
+        );
+
+        let relearning = REVIEW_CARD.replace(r#""state": 2"#, r#""state": "State.Relearning""#);
+        let card = from_pyfsrs_card(&relearning).unwrap().unwrap();
+        assert_eq!(card.grade, Grade::Again);
+
+        let unknown = REVIEW_CARD.replace(r#""state": 2"#, r#""state": "Suspended""#);
+        assert!(matches!(
+            from_pyfsrs_card(&unknown),
+            Err(ImportError::State(state)) if state == "Suspended"
+        ));
+        let index = REVIEW_CARD.replace(r#""state": 2"#, r#""state": 7"#);
+        assert!(matches!(
+            from_pyfsrs_card(&index),
+            Err(ImportError::State(_))
+        ));
+    }
+
+    #[test]
+    fn missing_last_review() {
+        let json = REVIEW_CARD.replace(
+            r#""last_review": "2022-11-29T12:30:00.359549+00:00""#,
+            r#""last_review": null"#,
+        );
+        let card = from_pyfsrs_card(&json).unwrap().unwrap();
+        assert_eq!(card.interval, Duration::days(10));
+    }
+
+    #[test]
+    fn new_card() {
+        let json = r#"{
+            "card_id": 1731221223359,
+            "state": 1,
+            "step": 0,
+            "stability": null,
+            "difficulty": null,
+            "due": "2024-11-10T06:47:03.359549+00:00",
+            "last_review": null
+        }"#;
+        assert_eq!(from_pyfsrs_card(json).unwrap(), None);
+
+        let json = r#"{"due": "2024-11-10T06:47:03+00:00", "stability": 0, "difficulty": 0,
+            "elapsed_days": 0, "scheduled_days": 0, "reps": 0, "lapses": 0, "state": 0}"#;
+        assert_eq!(from_pyfsrs_card(json).unwrap(), None);
+        assert!(matches!(from_pyfsrs_card("{}"), Err(ImportError::Json(_))));
+    }
+}
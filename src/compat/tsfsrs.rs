@@ -0,0 +1,100 @@
+//! Import from the TypeScript `ts-fsrs` package
+
+use super::ImportError;
+use crate::{Grade, Review};
+use chrono::{TimeZone, Utc};
+use serde::Deserialize;
+
+/// A review as logged by ts-fsrs
+#[derive(Deserialize)]
+struct TsReviewLog {
+    rating: i64,
+    review: i64,
+}
+
+impl TryFrom<TsReviewLog> for Review {
+    type Error = ImportError;
+
+    fn try_from(log: TsReviewLog) -> Result<Self, Self::Error> {
+        let grade = u8::try_from(log.rating)
+            .ok()
+            .and_then(|rating| Grade::try_from(rating).ok())
+            .ok_or(ImportError::Rating(log.rating))?;
+        let when = Utc
+            .timestamp_millis_opt(log.review)
+            .single()
+            .ok_or(ImportError::Timestamp(log.review))?;
+        Ok(Review::new(grade, when))
+    }
+}
+
+/// Convert an array of ts-fsrs `ReviewLog` objects into reviews
+///
+/// Ratings run from 1 for Again to 4 for Easy and review times are epoch
+/// milliseconds. Manual ratings and other values are rejected. The reviews
+/// are kept in the order given, ready for [Fsrs::reviewed](crate::Fsrs::reviewed).
+pub fn from_review_logs(json: &str) -> Result<Vec<Review>, ImportError> {
+    let logs: Vec<TsReviewLog> = serde_json::from_str(json)?;
+    logs.into_iter().map(Review::try_from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_review_logs;
+    use crate::{
+        compat::ImportError,
+        fsrs::tests::{string_to_utc, RoundFloat, WEIGHTS},
+        Fsrs, Grade, ParametersBuilder,
+    };
+
+    const LOGS: &str = r#"[
+        {"rating": 3, "state": 0, "due": 1669725000000, "stability": 0, "difficulty": 0,
+         "elapsed_days": 0, "last_elapsed_days": 0, "scheduled_days": 0,
+         "review": 1669725000000},
+        {"rating": 3, "state": 2, "due": 1669984200000, "stability": 3.0412,
+         "difficulty": 4.4909, "elapsed_days": 3, "last_elapsed_days": 0,
+         "scheduled_days": 3, "review": 1669984200000},
+        {"rating": 3, "state": 2, "due": 1671107400000, "stability": 13.0913,
+         "difficulty": 4.2666, "elapsed_days": 13, "last_elapsed_days": 3,
+         "scheduled_days": 13, "review": 1671107400000},
+        {"rating": 3, "state": 2, "due": 1675254600000, "stability": 48.1585,
+         "difficulty": 4.0575, "elapsed_days": 48, "last_elapsed_days": 13,
+         "scheduled_days": 48, "review": 1675254600000}
+    ]"#;
+
+    #[test]
+    fn replay() {
+        let reviews = from_review_logs(LOGS).unwrap();
+        assert_eq!(reviews.len(), 4);
+        assert!(reviews.iter().all(|review| review.grade == Grade::Good));
+        assert_eq!(
+            reviews[0].when,
+            string_to_utc("2022-11-29 12:30:00 +0000 UTC")
+        );
+
+        let fsrs = Fsrs::new(ParametersBuilder::new().weights(WEIGHTS).build());
+        let card = fsrs.reviewed(reviews).unwrap();
+        assert_eq!(card.interval.num_days(), 155);
+        assert_eq!(card.stability.round_float(4), 154.9373);
+        assert_eq!(card.difficulty.round_float(4), 3.8624);
+    }
+
+    #[test]
+    fn invalid_rating() {
+        for rating in [0, 5, -1] {
+            let json = format!(r#"[{{"rating": {rating}, "review": 1669725000000}}]"#);
+            assert!(matches!(
+                from_review_logs(&json),
+                Err(ImportError::Rating(r)) if r == rating
+            ));
+        }
+        assert!(matches!(
+            from_review_logs(r#"[{"rating": 1, "review": "soon"}]"#),
+            Err(ImportError::Json(_))
+        ));
+        assert!(matches!(
+            from_review_logs(&format!(r#"[{{"rating": 1, "review": {}}}]"#, i64::MAX)),
+            Err(ImportError::Timestamp(_))
+        ));
+    }
+}
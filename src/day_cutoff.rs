@@ -0,0 +1,211 @@
+use crate::{Duration, Parameters, Time};
+use chrono::{FixedOffset, NaiveDate, Utc};
+
+/// Local time of day when one study day ends and the next begins
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct DayCutoff {
+    /// Hour of the day from 0 to 23
+    pub(crate) hour: u32,
+    /// Seconds east of UTC for the local time zone
+    pub(crate) utc_offset: i32,
+}
+
+/// Local time of day when cards become due
+#[cfg(feature = "chrono-tz")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct DueTime {
+    /// Hour of the day from 0 to 23
+    pub(crate) hour: u32,
+    /// Minute of the hour from 0 to 59
+    pub(crate) minute: u32,
+    /// Time zone of the study schedule
+    pub(crate) tz: chrono_tz::Tz,
+}
+
+#[cfg(feature = "chrono-tz")]
+impl DueTime {
+    /// The due time on the local day the given number of days after the
+    /// review
+    ///
+    /// Times skipped by daylight saving time move forward to the first valid
+    /// minute, and repeated times use the earlier instant.
+    fn due(&self, reviewed_at: Time, days: i64) -> Option<Time> {
+        use chrono::{LocalResult, TimeZone};
+
+        let date = reviewed_at.with_timezone(&self.tz).date_naive() + Duration::days(days);
+        let mut local = date.and_hms_opt(self.hour, self.minute, 0)?;
+        for _ in 0..24 * 60 {
+            match self.tz.from_local_datetime(&local) {
+                LocalResult::Single(due) | LocalResult::Ambiguous(due, _) => {
+                    return Some(due.with_timezone(&Utc))
+                }
+                LocalResult::None => local += Duration::minutes(1),
+            }
+        }
+        None
+    }
+}
+
+#[cfg(feature = "chrono-tz")]
+impl PartialOrd for DueTime {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "chrono-tz")]
+impl Ord for DueTime {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.hour, self.minute, self.tz.name()).cmp(&(other.hour, other.minute, other.tz.name()))
+    }
+}
+
+impl DayCutoff {
+    /// The local date of the study day that the time falls in
+    fn study_day(&self, time: Time) -> Option<NaiveDate> {
+        let offset = FixedOffset::east_opt(self.utc_offset)?;
+        let local = time.with_timezone(&offset).naive_local();
+        Some((local - Duration::hours(self.hour.into())).date())
+    }
+}
+
+impl Parameters {
+    /// Number of study days from the first time to the second
+    ///
+    /// Study days begin at the day cutoff if there is one and at midnight
+    /// UTC otherwise, so two reviews a few hours apart can be on different
+    /// days.
+    pub(crate) fn study_days_between(&self, from: Time, to: Time) -> i64 {
+        let day = |time: Time| {
+            self.day_cutoff
+                .and_then(|cutoff| cutoff.study_day(time))
+                .unwrap_or_else(|| time.date_naive())
+        };
+        (day(to) - day(from)).num_days()
+    }
+
+    /// Interval from the review until the given number of days later
+    ///
+    /// With a due time of day, the interval ends at that time on the local
+    /// day the given number of days after the review. With a day cutoff, the
+    /// interval ends at the cutoff hour that starts the study day the given
+    /// number of days after the review's. Otherwise it is exactly that many
+    /// days.
+    pub(crate) fn align_interval(&self, reviewed_at: Time, days: i64) -> Duration {
+        #[cfg(feature = "chrono-tz")]
+        if let Some(due) = self.due_time.and_then(|time| time.due(reviewed_at, days)) {
+            return due - reviewed_at;
+        }
+
+        let Some(cutoff) = self.day_cutoff else {
+            return Duration::days(days);
+        };
+        let (Some(offset), Some(study_day)) = (
+            FixedOffset::east_opt(cutoff.utc_offset),
+            cutoff.study_day(reviewed_at),
+        ) else {
+            return Duration::days(days);
+        };
+
+        let due = (study_day + Duration::days(days))
+            .and_hms_opt(cutoff.hour, 0, 0)
+            .and_then(|due| due.and_local_timezone(offset).single());
+        match due {
+            Some(due) => due.with_timezone(&Utc) - reviewed_at,
+            None => Duration::days(days),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fsrs::tests::string_to_utc, Fsrs, Grade, ParametersBuilder};
+    use chrono::FixedOffset;
+
+    #[test]
+    fn before_cutoff() {
+        let parameters = ParametersBuilder::new()
+            .day_cutoff(4, FixedOffset::east_opt(0).unwrap())
+            .build();
+        let now = string_to_utc("2022-11-29 02:00:00 +0000 UTC");
+        let card = Fsrs::new(parameters).next_card(None, now, Grade::Again);
+        assert_eq!(card.due(), string_to_utc("2022-11-29 04:00:00 +0000 UTC"));
+    }
+
+    #[test]
+    fn on_time_after_cutoff() {
+        let parameters = ParametersBuilder::new()
+            .day_cutoff(4, FixedOffset::east_opt(0).unwrap())
+            .build();
+        let fsrs = Fsrs::new(parameters);
+        let now = string_to_utc("2022-11-29 02:00:00 +0000 UTC");
+        let card = fsrs.next_card(None, now, Grade::Again);
+
+        // Due two hours later, but on the next study day
+        let next = fsrs.next_card(Some(card), card.due(), Grade::Good);
+        assert_eq!(
+            next.stability,
+            parameters.next_stability(
+                card.difficulty,
+                card.stability,
+                card.retrievability(&parameters, card.due()),
+                Grade::Good
+            )
+        );
+        assert_ne!(
+            next.stability,
+            parameters.short_term_stability(card.stability, Grade::Good)
+        );
+    }
+
+    #[test]
+    fn after_cutoff() {
+        let offset = FixedOffset::west_opt(5 * 3600).unwrap();
+        let parameters = ParametersBuilder::new().day_cutoff(4, offset).build();
+        let now = string_to_utc("2022-11-29 20:30:00 +0000 UTC");
+        let card = Fsrs::new(parameters).next_card(None, now, Grade::Good);
+        assert_eq!(card.interval.num_days(), 2);
+        assert_eq!(card.due(), string_to_utc("2022-12-02 09:00:00 +0000 UTC"));
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn time_of_day() {
+        use chrono::{TimeZone, Utc};
+        use chrono_tz::America::New_York;
+
+        let parameters = ParametersBuilder::new()
+            .due_at_time_of_day(8, 0, New_York)
+            .build();
+        let fsrs = Fsrs::new(parameters);
+        let now = New_York
+            .with_ymd_and_hms(2022, 11, 29, 23, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        let card = fsrs.set_interval(fsrs.next_card(None, now, Grade::Good), 2, now);
+        let expected = New_York.with_ymd_and_hms(2022, 12, 1, 8, 0, 0).unwrap();
+        assert_eq!(card.due(), expected);
+
+        // 02:30 does not exist when daylight saving time starts on 2023-03-12
+        let parameters = ParametersBuilder::new()
+            .due_at_time_of_day(2, 30, New_York)
+            .build();
+        let fsrs = Fsrs::new(parameters);
+        let now = New_York
+            .with_ymd_and_hms(2023, 3, 10, 12, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        let card = fsrs.set_interval(fsrs.next_card(None, now, Grade::Good), 2, now);
+        let expected = New_York.with_ymd_and_hms(2023, 3, 12, 3, 0, 0).unwrap();
+        assert_eq!(card.due(), expected);
+
+        assert_eq!(
+            ParametersBuilder::new()
+                .due_at_time_of_day(8, 60, New_York)
+                .try_build(),
+            Err(vec![crate::ParamError::TimeOfDay(8, 60)])
+        );
+    }
+}
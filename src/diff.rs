@@ -0,0 +1,91 @@
+use crate::Parameters;
+
+/// Differences between two sets of [Parameters], from [Parameters::diff]
+#[derive(Debug, Clone, PartialEq, PartialOrd, Default)]
+pub struct ParametersDiff {
+    /// Index, old value, and new value of each weight that changed
+    pub weights: Vec<(usize, f64, f64)>,
+    /// Old and new desired retention, if it changed
+    pub retention: Option<(f64, f64)>,
+    /// Old and new maximum interval, if it changed
+    pub maximum_interval: Option<(i64, i64)>,
+}
+
+impl ParametersDiff {
+    /// Whether nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty() && self.retention.is_none() && self.maximum_interval.is_none()
+    }
+}
+
+impl Parameters {
+    /// Largest change in a weight or the retention that [Parameters::diff]
+    /// ignores
+    pub const DIFF_TOLERANCE: f64 = 1e-9;
+
+    /// Compare these parameters to `other`, such as before applying newly
+    /// trained weights
+    ///
+    /// Weights are compared up to the larger weight count of the two, with
+    /// weights that one version lacks counting as zero.
+    pub fn diff(&self, other: &Parameters) -> ParametersDiff {
+        let count = self.weights().len().max(other.weights().len());
+        let weights = (0..count)
+            .map(|i| (i, self.w[i], other.w[i]))
+            .filter(|(_, old, new)| !close(*old, *new))
+            .collect();
+        ParametersDiff {
+            weights,
+            retention: Some((self.retention, other.retention))
+                .filter(|(old, new)| !close(*old, *new)),
+            maximum_interval: Some((self.maximum_interval, other.maximum_interval))
+                .filter(|(old, new)| old != new),
+        }
+    }
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= Parameters::DIFF_TOLERANCE || a.to_bits() == b.to_bits()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Parameters, ParametersBuilder, ParametersDiff};
+
+    #[test]
+    fn diff() {
+        let default = Parameters::default();
+        assert!(default.diff(&default).is_empty());
+
+        let mut w = Parameters::DEFAULT_WEIGHTS;
+        w[3] += 0.5;
+        w[12] -= 0.01;
+        w[7] += 1e-12;
+        let changed = ParametersBuilder::new().weights(w).retention(0.85).build();
+        let diff = default.diff(&changed);
+        assert_eq!(
+            diff,
+            ParametersDiff {
+                weights: vec![
+                    (3, Parameters::DEFAULT_WEIGHTS[3], w[3]),
+                    (12, Parameters::DEFAULT_WEIGHTS[12], w[12]),
+                ],
+                retention: Some((0.9, 0.85)),
+                maximum_interval: None,
+            }
+        );
+
+        let capped = ParametersBuilder::new().maximum_interval(365).build();
+        assert_eq!(default.diff(&capped).maximum_interval, Some((36500, 365)));
+    }
+
+    #[test]
+    fn diff_versions() {
+        let mut w = [0.0; 21];
+        w[..19].copy_from_slice(&Parameters::DEFAULT_WEIGHTS);
+        w[20] = 0.2;
+        let fsrs_6 = ParametersBuilder::new().weights_v6(w).build();
+        let diff = Parameters::default().diff(&fsrs_6);
+        assert_eq!(diff.weights, [(20, 0.0, 0.2)]);
+    }
+}
@@ -0,0 +1,271 @@
+use crate::{Card, Fsrs, Grade, Parameters, ReviewHistory};
+use std::collections::BTreeMap;
+
+/// Reviews whose predicted retrievability fell in the same range
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct CalibrationBin {
+    /// Center of the bin's range of retrievability
+    pub midpoint: f64,
+    /// Mean predicted retrievability of the reviews
+    pub predicted: f64,
+    /// Portion of the reviews that were recalled
+    pub actual: f64,
+    /// Number of reviews
+    pub count: usize,
+}
+
+impl Parameters {
+    /// Number of equal-width retrievability bins used by
+    /// [Parameters::calibration_curve]
+    pub const CALIBRATION_BINS: usize = 20;
+
+    /// Mean log loss of the recall predicted for each review after the first
+    ///
+    /// Any grade other than [Grade::Again] counts as a successful recall.
+    /// Lower values mean the parameters better predict the reviews.
+    pub fn log_loss(&self, histories: &[ReviewHistory]) -> f64 {
+        let mut total = 0.0;
+        let mut count = 0usize;
+        self.for_each_prediction(histories, |r, recalled, _| {
+            let r = r.clamp(1e-6, 1.0 - 1e-6);
+            total -= if recalled { r.ln() } else { (1.0 - r).ln() };
+            count += 1;
+        });
+
+        if count == 0 {
+            0.0
+        } else {
+            total / count as f64
+        }
+    }
+
+    /// Root mean square calibration error of predicted recall, known as
+    /// RMSE (bins)
+    ///
+    /// Reviews are binned the same way as the FSRS benchmark, by the days
+    /// since the previous review, the number of earlier reviews on different
+    /// days, and the number of earlier lapses, each on a logarithmic scale.
+    /// Each bin compares its mean prediction to its observed recall rate and
+    /// is weighted by its number of reviews.
+    pub fn rmse_bins(&self, histories: &[ReviewHistory]) -> f64 {
+        let mut bins: BTreeMap<_, (f64, f64, usize)> = BTreeMap::new();
+        self.for_each_prediction(histories, |r, recalled, bin| {
+            let (predicted, actual, count) = bins.entry(bin).or_default();
+            *predicted += r;
+            *actual += if recalled { 1.0 } else { 0.0 };
+            *count += 1;
+        });
+
+        let mut total = 0.0;
+        let mut count = 0usize;
+        for (predicted, actual, n) in bins.into_values() {
+            let error = (predicted - actual) / n as f64;
+            total += n as f64 * error * error;
+            count += n;
+        }
+
+        if count == 0 {
+            0.0
+        } else {
+            (total / count as f64).sqrt()
+        }
+    }
+
+    /// Predicted against observed recall for plotting a reliability diagram
+    ///
+    /// Reviews are grouped into [Parameters::CALIBRATION_BINS] equal-width
+    /// bins by predicted retrievability, in order of increasing
+    /// retrievability. Empty bins are skipped.
+    pub fn calibration_curve(&self, histories: &[ReviewHistory]) -> Vec<CalibrationBin> {
+        let mut predicted = [0.0; Self::CALIBRATION_BINS];
+        let mut actual = [0.0; Self::CALIBRATION_BINS];
+        let mut counts = [0usize; Self::CALIBRATION_BINS];
+        self.for_each_prediction(histories, |r, recalled, _| {
+            let bin =
+                ((r * Self::CALIBRATION_BINS as f64) as usize).min(Self::CALIBRATION_BINS - 1);
+            predicted[bin] += r;
+            actual[bin] += if recalled { 1.0 } else { 0.0 };
+            counts[bin] += 1;
+        });
+
+        (0..Self::CALIBRATION_BINS)
+            .filter(|&bin| counts[bin] > 0)
+            .map(|bin| {
+                let n = counts[bin] as f64;
+                CalibrationBin {
+                    midpoint: (bin as f64 + 0.5) / Self::CALIBRATION_BINS as f64,
+                    predicted: predicted[bin] / n,
+                    actual: actual[bin] / n,
+                    count: counts[bin],
+                }
+            })
+            .collect()
+    }
+
+    /// Call `f` with the predicted retrievability, whether the card was
+    /// recalled, and the [benchmark_bin] for each review after the first
+    fn for_each_prediction(
+        &self,
+        histories: &[ReviewHistory],
+        mut f: impl FnMut(f64, bool, (u32, u32, u32)),
+    ) {
+        let fsrs = Fsrs::new(*self);
+        for history in histories {
+            let mut card: Option<Card> = None;
+            let mut long_term_reviews = 0;
+            let mut lapses = 0;
+            for review in history.reviews.iter() {
+                if let Some(card) = card {
+                    let elapsed_days = card.elapsed_days(review.when);
+                    let recalled = review.grade != Grade::Again;
+                    f(
+                        card.retrievability(self, review.when),
+                        recalled,
+                        benchmark_bin(elapsed_days, long_term_reviews + 1, lapses),
+                    );
+                    if elapsed_days > 0 {
+                        long_term_reviews += 1;
+                        lapses += u32::from(!recalled);
+                    }
+                }
+                card = Some(fsrs.next_card(card, review.when, review.grade));
+            }
+        }
+    }
+}
+
+/// The bin of a review for RMSE (bins), following the FSRS benchmark
+///
+/// `reviews` is one more than the number of earlier reviews on a later day
+/// than the review before them, and `lapses` counts those that were
+/// forgotten. Each value is rounded down to a power of a base that the
+/// benchmark chose to spread reviews evenly between bins.
+fn benchmark_bin(elapsed_days: i64, reviews: u32, lapses: u32) -> (u32, u32, u32) {
+    fn bin(value: f64, scale: f64, base: f64) -> f64 {
+        scale * base.powf((value.ln() / base.ln()).floor())
+    }
+
+    let elapsed_days = (bin(elapsed_days as f64, 2.48, 3.62) * 100.0).round() as u32;
+    let reviews = bin(reviews.into(), 1.99, 1.89).round() as u32;
+    let lapses = if lapses == 0 {
+        0
+    } else {
+        bin(lapses.into(), 1.65, 1.73).round() as u32
+    };
+    (elapsed_days, reviews, lapses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::benchmark_bin;
+    use crate::{
+        fsrs::tests::string_to_utc, optimizer::tests::synthetic_histories, Duration, Grade,
+        Parameters, Review, ReviewHistory,
+    };
+
+    #[test]
+    fn log_loss_of_perfect_predictions() {
+        let start = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let remembered = ReviewHistory::new(vec![
+            Review::new(Grade::Good, start),
+            Review::new(Grade::Good, start + Duration::hours(1)),
+            Review::new(Grade::Easy, start + Duration::hours(2)),
+        ]);
+        let forgotten = ReviewHistory::new(vec![
+            Review::new(Grade::Good, start),
+            Review::new(Grade::Again, start + Duration::days(1_000_000)),
+        ]);
+
+        let parameters = Parameters::default();
+        assert!(parameters.log_loss(&[remembered, forgotten]) < 0.01);
+        assert_eq!(parameters.log_loss(&[]), 0.0);
+    }
+
+    #[test]
+    fn log_loss_of_wrong_predictions() {
+        let start = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let history = ReviewHistory::new(vec![
+            Review::new(Grade::Good, start),
+            Review::new(Grade::Again, start + Duration::hours(1)),
+        ]);
+        assert!(Parameters::default().log_loss(&[history]) > 10.0);
+    }
+
+    #[test]
+    fn rmse_bins() {
+        let start = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let remembered = ReviewHistory::new(vec![
+            Review::new(Grade::Good, start),
+            Review::new(Grade::Good, start + Duration::hours(1)),
+            Review::new(Grade::Good, start + Duration::hours(2)),
+        ]);
+        let forgotten = ReviewHistory::new(vec![
+            Review::new(Grade::Good, start),
+            Review::new(Grade::Again, start + Duration::days(1_000_000)),
+        ]);
+
+        let parameters = Parameters::default();
+        assert!(parameters.rmse_bins(&[remembered.clone(), forgotten]) < 0.02);
+        assert_eq!(parameters.rmse_bins(&[]), 0.0);
+
+        let lapsed = ReviewHistory::new(vec![
+            Review::new(Grade::Good, start),
+            Review::new(Grade::Again, start + Duration::hours(1)),
+        ]);
+        assert!((parameters.rmse_bins(&[lapsed]) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn benchmark_bins() {
+        assert_eq!(benchmark_bin(0, 1, 0), (0, 2, 0));
+        assert_eq!(benchmark_bin(1, 2, 1), (248, 4, 2));
+        assert_eq!(benchmark_bin(3, 3, 2), (248, 4, 3));
+        assert_eq!(benchmark_bin(4, 4, 3), (898, 7, 5));
+        assert_eq!(benchmark_bin(30, 10, 5), (3250, 13, 5));
+    }
+
+    #[test]
+    fn rmse_bins_by_interval() {
+        let start = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let history = |days, grade| {
+            ReviewHistory::new(vec![
+                Review::new(Grade::Good, start),
+                Review::new(grade, start + Duration::days(days)),
+            ])
+        };
+        let parameters = Parameters::default();
+        let stability = parameters.init_stability(Grade::Good);
+        let short = parameters.forgetting_curve(1.0, stability);
+        let long = parameters.forgetting_curve(2.0, stability);
+
+        // One and two days share a bin, but five days does not
+        let histories = [
+            history(1, Grade::Good),
+            history(2, Grade::Again),
+            history(5, Grade::Good),
+        ];
+        let shared = (short + long) / 2.0 - 0.5;
+        let apart = parameters.forgetting_curve(5.0, stability) - 1.0;
+        let expected = ((2.0 * shared * shared + apart * apart) / 3.0).sqrt();
+        assert!((parameters.rmse_bins(&histories) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn calibration_curve() {
+        let parameters = Parameters::default();
+        let histories = synthetic_histories(parameters, 100);
+        let curve = parameters.calibration_curve(&histories);
+        assert!(!curve.is_empty());
+        assert!(curve
+            .windows(2)
+            .all(|pair| pair[0].midpoint < pair[1].midpoint));
+        let reviews: usize = histories.iter().map(|h| h.reviews.len() - 1).sum();
+        assert_eq!(curve.iter().map(|bin| bin.count).sum::<usize>(), reviews);
+        for bin in &curve {
+            let half_width = 0.5 / Parameters::CALIBRATION_BINS as f64;
+            assert!((bin.predicted - bin.midpoint).abs() <= half_width + 1e-12);
+            assert!((0.0..=1.0).contains(&bin.actual));
+        }
+        assert!(parameters.calibration_curve(&[]).is_empty());
+    }
+}
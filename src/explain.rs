@@ -0,0 +1,144 @@
+use crate::{Card, Fsrs, Grade, Time};
+
+/// The intermediate values behind a call to [Fsrs::next_card_explained]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Explanation {
+    /// Retrievability at the time of the review, or None for a new card
+    pub retrievability: Option<f64>,
+    /// Whether the card was reviewed on the same day as its last review,
+    /// which uses [Parameters::short_term_stability](crate::Parameters::short_term_stability)
+    pub same_day: bool,
+    /// Stability before the review, or None for a new card
+    pub previous_stability: Option<f64>,
+    /// Stability after the review
+    pub stability: f64,
+    /// Difficulty before the review, or None for a new card
+    pub previous_difficulty: Option<f64>,
+    /// Difficulty after the review
+    pub difficulty: f64,
+    /// Fractional days for retrievability to fall to the desired retention
+    pub raw_interval: f64,
+    /// Days after rounding, interval multipliers, and the maximum interval
+    pub graded_interval: f64,
+    /// Days after fuzz, which is the graded interval if fuzz is disabled
+    pub fuzzed_interval: f64,
+    /// Whether the stability was raised to the minimum stability
+    pub minimum_stability: bool,
+    /// Whether the interval was shortened to the maximum interval
+    pub maximum_interval: bool,
+    /// Whether the interval was raised to one day or the graduating or easy
+    /// interval
+    pub minimum_interval: bool,
+}
+
+impl<C> Fsrs<C> {
+    /// Compute the new state after a review like [Fsrs::next_card], along
+    /// with the values that led to it
+    pub fn next_card_explained(
+        &self,
+        card: Option<Card>,
+        now: Time,
+        grade: Grade,
+    ) -> (Card, Explanation) {
+        let next = self.next_card(card, now, grade);
+        let p = &self.card_parameters(next.retention);
+        let (_, stability) = self.next_memory_state(card, now, grade);
+        let elapsed_days = card.map_or(0, |card| card.elapsed_days(now));
+        let raw_interval = p.inverse_forgetting_curve(next.stability, p.retention);
+        let graded_interval = p.graded_interval(next.stability, grade);
+        let fuzzed_interval = p.fuzz_interval(graded_interval, elapsed_days, now, next.stability);
+        let minimum = self.minimum_interval(card, grade) as f64;
+
+        let explanation = Explanation {
+            retrievability: card.map(|card| card.retrievability(self.parameters(), now)),
+            same_day: card.is_some_and(|card| p.study_days_between(card.reviewed_at, now) <= 0),
+            previous_stability: card.map(|card| card.stability),
+            stability: next.stability,
+            previous_difficulty: card.map(|card| card.difficulty),
+            difficulty: next.difficulty,
+            raw_interval,
+            graded_interval,
+            fuzzed_interval,
+            minimum_stability: stability < p.minimum_stability,
+            maximum_interval: p
+                .interval_rounding
+                .apply(p.unrounded_interval(next.stability, grade))
+                > p.maximum_interval_days() as f64,
+            minimum_interval: fuzzed_interval.is_nan() || fuzzed_interval < minimum,
+        };
+        (next, explanation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fsrs::tests::string_to_utc, Fsrs, Grade, ParametersBuilder};
+
+    #[test]
+    fn explained() {
+        let fsrs = Fsrs::default();
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let (first, explanation) = fsrs.next_card_explained(None, now, Grade::Good);
+        assert_eq!(first, fsrs.next_card(None, now, Grade::Good));
+        assert_eq!(explanation.retrievability, None);
+        assert_eq!(explanation.previous_stability, None);
+        assert_eq!(explanation.stability, first.stability);
+        assert_eq!(explanation.difficulty, first.difficulty);
+
+        let later = first.due();
+        let (second, explanation) = fsrs.next_card_explained(Some(first), later, Grade::Good);
+        assert_eq!(second, fsrs.next_card(Some(first), later, Grade::Good));
+        assert_eq!(explanation.stability, second.stability);
+        assert_eq!(explanation.previous_stability, Some(first.stability));
+        assert_eq!(
+            explanation.retrievability,
+            Some(first.retrievability(&Default::default(), later))
+        );
+        assert_eq!(
+            explanation.graded_interval,
+            second.interval.num_days() as f64
+        );
+        assert!((explanation.raw_interval - explanation.graded_interval).abs() <= 0.5);
+        assert!(!explanation.same_day);
+        assert!(!explanation.maximum_interval);
+        assert!(!explanation.minimum_interval);
+
+        let capped = Fsrs::new(ParametersBuilder::new().maximum_interval(5).build());
+        let (card, explanation) = capped.next_card_explained(Some(second), later, Grade::Easy);
+        assert!(explanation.same_day);
+        assert!(explanation.maximum_interval);
+        assert_eq!(card.interval.num_days(), 5);
+
+        // The maximum interval applies after the interval multipliers
+        let raw = |grade| {
+            let (_, explanation) = fsrs.next_card_explained(Some(first), later, grade);
+            explanation.raw_interval.round() as i64
+        };
+        let easy = raw(Grade::Easy);
+        let boosted = Fsrs::new(
+            ParametersBuilder::new()
+                .maximum_interval(easy + 1)
+                .easy_bonus(2.0)
+                .build(),
+        );
+        let (card, explanation) = boosted.next_card_explained(Some(first), later, Grade::Easy);
+        assert!(explanation.maximum_interval);
+        assert_eq!(card.interval.num_days(), easy + 1);
+
+        let hard = raw(Grade::Hard);
+        let shortened = Fsrs::new(
+            ParametersBuilder::new()
+                .maximum_interval(hard - 1)
+                .hard_factor(0.5)
+                .build(),
+        );
+        let (card, explanation) = shortened.next_card_explained(Some(first), later, Grade::Hard);
+        assert!(!explanation.maximum_interval);
+        assert!(card.interval.num_days() < hard - 1);
+
+        let graduating = Fsrs::new(ParametersBuilder::new().graduating_interval(7).build());
+        let (card, explanation) = graduating.next_card_explained(None, now, Grade::Good);
+        assert!(explanation.minimum_interval);
+        assert_eq!(card.interval.num_days(), 7);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{Grade, ReviewLog, Time};
+use serde::{Deserialize, Serialize};
+use std::io::{self, Read, Write};
+
+/// One row of a review history CSV file
+#[derive(Serialize, Deserialize)]
+struct Row {
+    grade: Grade,
+    reviewed_at: Time,
+    elapsed_days: i64,
+    scheduled_days: i64,
+    due: Time,
+    stability: f64,
+    difficulty: f64,
+}
+
+impl From<&ReviewLog> for Row {
+    fn from(log: &ReviewLog) -> Self {
+        Self {
+            grade: log.grade,
+            reviewed_at: log.reviewed_at,
+            elapsed_days: log.elapsed_days,
+            scheduled_days: log.scheduled_days,
+            due: log.due,
+            stability: log.stability,
+            difficulty: log.difficulty,
+        }
+    }
+}
+
+impl From<Row> for ReviewLog {
+    fn from(row: Row) -> Self {
+        Self {
+            grade: row.grade,
+            reviewed_at: row.reviewed_at,
+            elapsed_days: row.elapsed_days,
+            scheduled_days: row.scheduled_days,
+            due: row.due,
+            stability: row.stability,
+            difficulty: row.difficulty,
+        }
+    }
+}
+
+/// Write review logs as CSV
+///
+/// The first line is the header
+/// `grade,reviewed_at,elapsed_days,scheduled_days,due,stability,difficulty`.
+/// Grades are written by name, such as `good`, and times in RFC 3339.
+pub fn export_reviews(logs: &[ReviewLog], writer: impl Write) -> io::Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    for log in logs {
+        writer.serialize(Row::from(log))?;
+    }
+    writer.flush()
+}
+
+/// Read review logs written by [export_reviews]
+///
+/// Columns are matched by the header, so they may be in any order. Grades
+/// may be given by name or by number from 1 for Again to 4 for Easy.
+pub fn import_reviews(reader: impl Read) -> Result<Vec<ReviewLog>, csv::Error> {
+    csv::Reader::from_reader(reader)
+        .deserialize()
+        .map(|row| row.map(|row: Row| row.into()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export_reviews, import_reviews};
+    use crate::{fsrs::tests::string_to_utc, Duration, Fsrs, Grade, ReviewLog};
+
+    #[test]
+    fn round_trip() {
+        let fsrs = Fsrs::default();
+        let mut now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let mut card = None;
+        let mut logs = vec![];
+        for grade in [Grade::Good, Grade::Again, Grade::Hard, Grade::Easy] {
+            let next = fsrs.next_card(card, now, grade);
+            logs.push(ReviewLog::new(card, next));
+            card = Some(next);
+            now = next.due() + Duration::hours(3);
+        }
+
+        let mut csv = vec![];
+        export_reviews(&logs, &mut csv).unwrap();
+        let text = String::from_utf8(csv).unwrap();
+        assert!(text.starts_with(
+            "grade,reviewed_at,elapsed_days,scheduled_days,due,stability,difficulty\ngood,2022-11-29T12:30:00Z,"
+        ));
+        assert_eq!(import_reviews(text.as_bytes()).unwrap(), logs);
+    }
+
+    #[test]
+    fn numeric_grades() {
+        let text = "reviewed_at,grade,elapsed_days,scheduled_days,due,stability,difficulty\n\
+                    2022-11-29T12:30:00Z,3,0,3,2022-12-02T12:30:00Z,3.17,5.28\n";
+        let logs = import_reviews(text.as_bytes()).unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].grade, Grade::Good);
+        assert_eq!(logs[0].due, string_to_utc("2022-12-02 12:30:00 +0000 UTC"));
+        assert!(import_reviews("grade\n5\n".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+use crate::Duration;
+
+/// Short label for an interval, such as "10m", "2d", or "1.2y"
+///
+/// Units and thresholds follow Anki's answer buttons: seconds, minutes, hours,
+/// days, months of 30.417 days, and years of 365 days. Months and years show
+/// one decimal place and smaller units are rounded to whole numbers.
+pub fn format_interval(interval: Duration) -> String {
+    const MINUTE: f64 = 60.0;
+    const HOUR: f64 = 60.0 * MINUTE;
+    const DAY: f64 = 24.0 * HOUR;
+    const MONTH: f64 = 30.417 * DAY;
+    const YEAR: f64 = 365.0 * DAY;
+    /// Length in seconds, suffix, and whether to show a decimal place
+    const UNITS: [(f64, &str, bool); 6] = [
+        (1.0, "s", false),
+        (MINUTE, "m", false),
+        (HOUR, "h", false),
+        (DAY, "d", false),
+        (MONTH, "mo", true),
+        (YEAR, "y", true),
+    ];
+
+    let seconds = interval.num_seconds().abs() as f64;
+    let mut unit = UNITS
+        .iter()
+        .rposition(|&(length, ..)| seconds >= length)
+        .unwrap_or(0);
+    loop {
+        let (length, suffix, decimal) = UNITS[unit];
+        let value = if decimal {
+            (seconds / length * 10.0).round() / 10.0
+        } else {
+            (seconds / length).round()
+        };
+        // Values that round up to the next unit are shown in that unit
+        match UNITS.get(unit + 1) {
+            Some(&(next, ..)) if value * length >= next => unit += 1,
+            _ => return format!("{value}{suffix}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_interval;
+    use crate::Duration;
+
+    #[test]
+    fn format() {
+        assert_eq!(format_interval(Duration::seconds(30)), "30s");
+        assert_eq!(format_interval(Duration::minutes(1)), "1m");
+        assert_eq!(format_interval(Duration::minutes(10)), "10m");
+        assert_eq!(format_interval(Duration::hours(5)), "5h");
+        assert_eq!(format_interval(Duration::days(1)), "1d");
+        assert_eq!(format_interval(Duration::days(45)), "1.5mo");
+        assert_eq!(format_interval(Duration::days(61)), "2mo");
+        assert_eq!(format_interval(Duration::days(400)), "1.1y");
+        assert_eq!(format_interval(Duration::zero()), "0s");
+    }
+
+    #[test]
+    fn unit_boundaries() {
+        assert_eq!(format_interval(Duration::seconds(59)), "59s");
+        assert_eq!(format_interval(Duration::seconds(3570)), "1h");
+        assert_eq!(format_interval(Duration::seconds(3599)), "1h");
+        assert_eq!(format_interval(Duration::minutes(89)), "1h");
+        assert_eq!(format_interval(Duration::seconds(86399)), "1d");
+        assert_eq!(format_interval(Duration::hours(23)), "23h");
+        assert_eq!(format_interval(Duration::days(30)), "30d");
+        assert_eq!(
+            format_interval(Duration::days(365) - Duration::hours(1)),
+            "1y"
+        );
+        assert_eq!(format_interval(Duration::days(355)), "11.7mo");
+    }
+}
@@ -1,62 +1,436 @@
-use crate::{Card, Duration, Grade, Parameters, Time};
+use crate::{
+    Card, Cards, Clock, Duration, Grade, ParamError, Parameters, Session, SystemClock, Time,
+};
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
 
 /// The FSRS algorithm
-#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
-pub struct Fsrs(Parameters);
+///
+/// The [Clock] provides the review time for [Fsrs::schedule]. Methods that
+/// take the review time explicitly ignore it.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Fsrs<C = SystemClock> {
+    parameters: Parameters,
+    clock: C,
+}
 
 impl Fsrs {
+    /// Longest interval in days that can be scheduled, regardless of the
+    /// maximum interval parameter, so that due dates remain representable
+    pub const MAX_INTERVAL_DAYS: i64 = 10_000_000;
+
     /// Create a new [Fsrs] with the given parameters
     pub const fn new(parameters: Parameters) -> Self {
-        Self(parameters)
+        Self::with_clock(parameters, SystemClock)
+    }
+
+    /// Create a new [Fsrs], checking the parameters with
+    /// [Parameters::validate]
+    pub fn try_new(parameters: Parameters) -> Result<Self, FsrsError> {
+        parameters
+            .validate()
+            .map_err(FsrsError::InvalidParameters)?;
+        Ok(Self::new(parameters))
+    }
+}
+
+impl Default for Fsrs {
+    fn default() -> Self {
+        Self::new(Parameters::default())
+    }
+}
+
+impl<C: Clock> Fsrs<C> {
+    /// Compute the new state after a review that happens now
+    ///
+    /// # Parameters
+    ///
+    /// - `card`: The card being reviewed, or None if it's the first review
+    /// - `grade`: The difficulty of the review
+    pub fn schedule(&self, card: Option<Card>, grade: Grade) -> Card {
+        self.next_card(card, self.clock.now(), grade)
+    }
+}
+
+impl<C> Fsrs<C> {
+    /// Create a new [Fsrs] with the given parameters and clock
+    pub const fn with_clock(parameters: Parameters, clock: C) -> Self {
+        Self { parameters, clock }
+    }
+
+    /// The parameters used for scheduling
+    pub const fn parameters(&self) -> &Parameters {
+        &self.parameters
     }
 
     /// Compute the new state after a review
     ///
+    /// The given card is not modified, so undoing a review only requires
+    /// keeping the previous [Card] around.
+    ///
     /// # Parameters
     ///
     /// - `card`: The card being reviewed, or None if it's the first review
     /// - `now`: The time the card is reviewed
     /// - `grade`: The difficulty of the review
     pub fn next_card(&self, card: Option<Card>, now: Time, grade: Grade) -> Card {
-        let p = &self.0;
-
-        let (difficulty, stability) = if let Some(card) = card {
-            let Card {
-                stability,
-                difficulty,
-                ..
-            } = card;
-            (
-                p.next_difficulty(difficulty, grade),
-                p.next_stability(difficulty, stability, card.retrievability(p, now), grade),
-            )
-        } else {
-            (p.init_difficulty(grade), p.init_stability(grade))
-        };
+        let p = &self.parameters;
+        let (difficulty, stability) = self.next_memory_state(card, now, grade);
+        let stability = stability.max(p.minimum_stability);
 
+        let elapsed_days = card.map_or(0, |card| card.elapsed_days(now));
+        let minimum = self.minimum_interval(card, grade);
+        let retention = card.and_then(|card| card.retention);
+        let lapses = match card {
+            Some(card) if grade == Grade::Again => card.lapses.saturating_add(1),
+            Some(card) => card.lapses,
+            None => 0,
+        };
         Card {
             difficulty,
             stability,
             grade,
             reviewed_at: now,
-            interval: Duration::days(p.next_interval(stability) as i64),
+            interval: self.interval(retention, stability, grade, elapsed_days, now, minimum),
+            lapses,
+            retention,
+        }
+    }
+
+    /// The difficulty and stability after a review, before the minimum
+    /// stability is applied
+    pub(crate) fn next_memory_state(
+        &self,
+        card: Option<Card>,
+        now: Time,
+        grade: Grade,
+    ) -> (f64, f64) {
+        let p = &self.parameters;
+        let Some(card) = card else {
+            return (p.init_difficulty(grade), p.init_stability(grade));
+        };
+        let Card {
+            stability,
+            difficulty,
+            ..
+        } = card;
+        let stability = if p.study_days_between(card.reviewed_at, now) <= 0 {
+            p.short_term_stability(stability, grade)
+        } else {
+            p.next_stability(difficulty, stability, card.retrievability(p, now), grade)
+        };
+        (p.next_difficulty(difficulty, grade), stability)
+    }
+
+    /// Compute the new state after a review, moving the due date towards
+    /// the day with the fewest reviews
+    ///
+    /// Each day that fuzz could choose for the interval is passed to
+    /// `due_count` as the time the card would be due, and should return how
+    /// many reviews are already scheduled on that day. The least loaded day
+    /// is chosen, preferring the unfuzzed interval and then shorter
+    /// intervals on ties. Intervals too short for fuzz are unchanged. This
+    /// takes the place of random fuzz, so the result is the same whether or
+    /// not fuzz is enabled.
+    pub fn next_card_balanced(
+        &self,
+        card: Option<Card>,
+        now: Time,
+        grade: Grade,
+        mut due_count: impl FnMut(Time) -> usize,
+    ) -> Card {
+        let next = self.next_card(card, now, grade);
+        let p = &self.card_parameters(next.retention);
+        let interval = p.graded_interval(next.stability, grade);
+        if interval.is_nan() || interval < 2.5 {
+            return next;
+        }
+
+        let elapsed_days = card.map_or(0, |card| card.elapsed_days(now));
+        let (min, max) = p.fuzz_range(interval, elapsed_days);
+        let min = min.max(self.minimum_interval(card, grade));
+        let max = max.max(min).min(Fsrs::MAX_INTERVAL_DAYS);
+        let best = (min..=max)
+            .map(|days| {
+                let distance = (days as f64 - interval).abs();
+                let aligned = p.align_interval(now, days);
+                (due_count(now + aligned), distance, days, aligned)
+            })
+            .min_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)).then(a.2.cmp(&b.2)));
+        match best {
+            Some((.., interval)) => Card { interval, ..next },
+            None => next,
+        }
+    }
+
+    /// Compute the new state after a review, checking the card first
+    ///
+    /// Since the fields of [Card] are public, a card can be given a memory
+    /// state that FSRS would never produce. Such cards are rejected with
+    /// [FsrsError::InvalidState] rather than producing a meaningless
+    /// schedule. Otherwise this is the same as [Fsrs::next_card].
+    pub fn try_next_card(
+        &self,
+        card: Option<Card>,
+        now: Time,
+        grade: Grade,
+    ) -> Result<Card, FsrsError> {
+        let card = card.map(Card::validate).transpose()?;
+        Ok(self.next_card(card, now, grade))
+    }
+
+    /// Recompute the interval of a card after the parameters change
+    ///
+    /// The memory state and review time are kept, so this does not count as
+    /// a review. The new interval starts from the card's last review.
+    pub fn reschedule(&self, card: Card) -> Card {
+        Card {
+            interval: self.interval(
+                card.retention,
+                card.stability,
+                card.grade,
+                0,
+                card.reviewed_at,
+                1,
+            ),
+            ..card
+        }
+    }
+
+    /// Schedule the card for the given number of days from now, overriding
+    /// FSRS
+    ///
+    /// The stability is set so that the card's retrievability falls to the
+    /// desired retention when it comes due, keeping later reviews
+    /// consistent with the chosen interval. The difficulty, grade, and
+    /// lapses are kept. Intervals of less than a day are treated as one day.
+    pub fn set_interval(&self, card: Card, days: i64, now: Time) -> Card {
+        let p = &self.card_parameters(card.retention);
+        let days = days.clamp(1, Fsrs::MAX_INTERVAL_DAYS);
+        let stability = days as f64 / p.inverse_forgetting_curve(1.0, p.retention);
+        Card {
+            reviewed_at: now,
+            interval: p.align_interval(now, days),
+            stability: stability.max(p.minimum_stability),
+            ..card
         }
     }
 
+    /// Shortest interval in days after reviewing the card
+    ///
+    /// New cards that are not forgotten use the graduating or easy interval.
+    pub(crate) fn minimum_interval(&self, card: Option<Card>, grade: Grade) -> i64 {
+        let p = &self.parameters;
+        let minimum = match (card, grade) {
+            (Some(_), _) | (None, Grade::Again) => 1,
+            (None, Grade::Hard | Grade::Good) => p.graduating_interval,
+            (None, Grade::Easy) => p.easy_interval,
+        };
+        minimum.min(p.maximum_interval_days())
+    }
+
+    /// The parameters for scheduling a card with the given retention
+    /// override
+    pub(crate) fn card_parameters(&self, retention: Option<f64>) -> Parameters {
+        match retention {
+            Some(retention) => Parameters {
+                retention,
+                ..self.parameters
+            },
+            None => self.parameters,
+        }
+    }
+
+    /// The interval for a memory with the given stability, in days no
+    /// shorter than `minimum`
+    ///
+    /// Non-finite stability produces the shortest or longest interval rather
+    /// than an invalid duration.
+    fn interval(
+        &self,
+        retention: Option<f64>,
+        stability: f64,
+        grade: Grade,
+        elapsed_days: i64,
+        reviewed_at: Time,
+        minimum: i64,
+    ) -> Duration {
+        let p = &self.card_parameters(retention);
+        let interval = p.fuzz_interval(
+            p.graded_interval(stability, grade),
+            elapsed_days,
+            reviewed_at,
+            stability,
+        );
+        let interval = if interval.is_nan() { 1.0 } else { interval };
+        let interval = interval
+            .max(minimum as f64)
+            .min(Fsrs::MAX_INTERVAL_DAYS as f64);
+        p.align_interval(reviewed_at, interval as i64)
+    }
+
+    /// Compute the new state for each possible grade of a review
+    ///
+    /// # Parameters
+    ///
+    /// - `card`: The card being reviewed, or None if it's the first review
+    /// - `now`: The time the card is reviewed
+    pub fn preview(&self, card: Option<Card>, now: Time) -> Cards<Card> {
+        Cards::from_fn(|grade| self.next_card(card, now, grade))
+    }
+
+    /// Whole days until the card is due for each possible grade of a
+    /// review, from Again to Easy
+    ///
+    /// This is [Fsrs::preview] reduced to the intervals, such as for
+    /// labelling grade buttons.
+    pub fn next_intervals(&self, card: Option<Card>, now: Time) -> [i64; 4] {
+        self.preview(card, now)
+            .map(|(_, card)| card.interval.num_days())
+            .into_array()
+    }
+
+    /// Compute the new state of each card after reviews with the same grade
+    ///
+    /// With the `rayon` feature, the cards are scheduled in parallel.
+    #[cfg(feature = "rayon")]
+    pub fn schedule_batch(&self, cards: &[Card], now: Time, grade: Grade) -> Vec<Card>
+    where
+        C: Sync,
+    {
+        use rayon::prelude::*;
+        cards
+            .par_iter()
+            .map(|&card| self.next_card(Some(card), now, grade))
+            .collect()
+    }
+
+    /// Compute the new state of each card after reviews with the same grade
+    ///
+    /// With the `rayon` feature, the cards are scheduled in parallel.
+    #[cfg(not(feature = "rayon"))]
+    pub fn schedule_batch(&self, cards: &[Card], now: Time, grade: Grade) -> Vec<Card> {
+        cards
+            .iter()
+            .map(|&card| self.next_card(Some(card), now, grade))
+            .collect()
+    }
+
+    /// Sort cards from least to most likely to be recalled at the given time
+    ///
+    /// The sort is stable. Cards whose retrievability is NaN, such as from a
+    /// NaN stability, come last.
+    pub fn sort_by_retrievability(&self, cards: &mut [Card], now: Time) {
+        let p = &self.parameters;
+        // Compute each retrievability once rather than in every comparison
+        let mut keyed: Vec<_> = cards
+            .iter()
+            .map(|&card| (card.retrievability(p, now), card))
+            .collect();
+        keyed.sort_by(|(a, _), (b, _)| match (a.is_nan(), b.is_nan()) {
+            (false, false) => a.total_cmp(b),
+            (nan_a, nan_b) => nan_a.cmp(&nan_b),
+        });
+        for (card, (_, sorted)) in cards.iter_mut().zip(keyed) {
+            *card = sorted;
+        }
+    }
+
+    /// Start reviewing the card, or a new card if None, keeping track of its
+    /// state between reviews
+    pub fn session(&self, card: Option<Card>) -> Session<'_, C> {
+        Session::new(self, card)
+    }
+
     /// Compute the new state after the given reviews
     ///
-    /// Returns None when the iterator produces no values.
-    pub fn reviewed(&self, reviews: impl IntoIterator<Item = Review>) -> Option<Card> {
+    /// Each review's time is used as the time elapsed since the previous
+    /// review, regardless of when the card was scheduled. Returns None when
+    /// the iterator produces no values.
+    pub fn reviewed(&self, reviews: impl IntoIterator<Item = impl Into<Review>>) -> Option<Card> {
         let mut card = None;
         for review in reviews.into_iter() {
+            let review = review.into();
             card = Some(self.next_card(card, review.when, review.grade));
         }
         card
     }
+
+    /// Like [Fsrs::reviewed], but sorting the reviews by time first
+    ///
+    /// Imported logs are not always in chronological order, and a review that
+    /// comes before the previous one would otherwise see a negative elapsed
+    /// time. Reviews at the same time keep their order.
+    pub fn reviewed_sorted(
+        &self,
+        reviews: impl IntoIterator<Item = impl Into<Review>>,
+    ) -> Option<Card> {
+        let mut reviews: Vec<Review> = reviews.into_iter().map(Into::into).collect();
+        reviews.sort_by_key(|review| review.when);
+        self.reviewed(reviews)
+    }
+
+    /// Like [Fsrs::reviewed], but failing with [FsrsError::OutOfOrder] for
+    /// the first review that is earlier than the one before it
+    pub fn try_reviewed(
+        &self,
+        reviews: impl IntoIterator<Item = impl Into<Review>>,
+    ) -> Result<Option<Card>, FsrsError> {
+        let mut card: Option<Card> = None;
+        for review in reviews.into_iter() {
+            let review = review.into();
+            if card.is_some_and(|card| review.when < card.reviewed_at) {
+                return Err(FsrsError::OutOfOrder(review));
+            }
+            card = Some(self.next_card(card, review.when, review.grade));
+        }
+        Ok(card)
+    }
+}
+
+/// Failure to schedule a card
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum FsrsError {
+    /// The parameters failed [Parameters::validate]
+    InvalidParameters(Vec<ParamError>),
+    /// The card's stability is not positive and finite, its difficulty is
+    /// outside [1, 10], its retention is outside (0, 1), or its interval is
+    /// negative
+    InvalidState(Card),
+    /// The review took place before the one preceding it
+    OutOfOrder(Review),
+}
+
+impl Display for FsrsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FsrsError::InvalidParameters(errors) => {
+                f.write_str("invalid parameters")?;
+                for (i, error) in errors.iter().enumerate() {
+                    let separator = if i == 0 { ": " } else { ", " };
+                    write!(f, "{separator}{error}")?;
+                }
+                Ok(())
+            }
+            FsrsError::InvalidState(card) => write!(
+                f,
+                "invalid card with stability {} and difficulty {}",
+                card.stability, card.difficulty
+            ),
+            FsrsError::OutOfOrder(review) => {
+                write!(f, "review at {} is earlier than the last", review.when)
+            }
+        }
+    }
 }
 
+impl Error for FsrsError {}
+
 /// Information about a card review
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Review {
     /// Difficulty of the review
     pub grade: Grade,
@@ -71,9 +445,18 @@ impl Review {
     }
 }
 
+impl From<(Grade, Time)> for Review {
+    fn from((grade, when): (Grade, Time)) -> Self {
+        Self::new(grade, when)
+    }
+}
+
 #[cfg(test)]
-mod tests {
-    use crate::{Fsrs, Grade, Parameters, Time};
+pub(crate) mod tests {
+    use super::FsrsError;
+    use crate::{
+        Card, Clock, Duration, Fsrs, Grade, ParamError, Parameters, ParametersBuilder, Review, Time,
+    };
     use chrono::{DateTime, TimeZone, Utc};
 
     pub const TEST_GRADES: [Grade; 13] = [
@@ -116,7 +499,7 @@ mod tests {
     #[test]
     fn longterm() {
         let params = Parameters {
-            w: WEIGHTS,
+            w: WEIGHTS.into(),
             ..Default::default()
         };
 
@@ -150,4 +533,506 @@ mod tests {
         assert_eq!(stability_history, expected_stability);
         assert_eq!(difficulty_history, expected_difficulty);
     }
+
+    #[test]
+    fn errors() {
+        let invalid = ParametersBuilder::new()
+            .retention(1.5)
+            .maximum_interval(0)
+            .build();
+        let error = Fsrs::try_new(invalid).unwrap_err();
+        assert_eq!(
+            error,
+            FsrsError::InvalidParameters(vec![
+                ParamError::Retention(1.5),
+                ParamError::MaximumInterval(0)
+            ])
+        );
+        assert_eq!(
+            error.to_string(),
+            "invalid parameters: retention 1.5 is outside (0, 1), \
+             maximum interval of 0 days is less than one day"
+        );
+
+        // Unvalidated parameters still schedule cards rather than panicking
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        for maximum_interval in [0, -5] {
+            let fsrs = Fsrs::new(
+                ParametersBuilder::new()
+                    .maximum_interval(maximum_interval)
+                    .enable_fuzz(true)
+                    .build(),
+            );
+            for grade in Grade::all() {
+                let card = fsrs.next_card(None, now, grade);
+                assert_eq!(card.interval.num_days(), 1);
+                let later = fsrs.next_card(Some(card), now + Duration::days(30), grade);
+                assert_eq!(later.interval.num_days(), 1);
+            }
+        }
+
+        let fsrs = Fsrs::try_new(Parameters::default()).unwrap();
+        let card = fsrs.try_next_card(None, now, Grade::Good).unwrap();
+        assert_eq!(card, fsrs.next_card(None, now, Grade::Good));
+        assert_eq!(
+            fsrs.try_next_card(Some(card), now, Grade::Good),
+            Ok(fsrs.next_card(Some(card), now, Grade::Good))
+        );
+
+        let nan = Card {
+            stability: f64::NAN,
+            ..card
+        };
+        assert!(matches!(
+            fsrs.try_next_card(Some(nan), now, Grade::Good),
+            Err(FsrsError::InvalidState(_))
+        ));
+        let difficulty = Card {
+            difficulty: 0.0,
+            ..card
+        };
+        assert!(matches!(
+            fsrs.try_next_card(Some(difficulty), now, Grade::Good),
+            Err(FsrsError::InvalidState(_))
+        ));
+    }
+
+    #[test]
+    fn load_balancing() {
+        let fsrs = Fsrs::default();
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = fsrs.next_card(None, now, Grade::Good);
+        let later = now + card.interval;
+        let base = fsrs.next_card(Some(card), later, Grade::Good);
+        let base_due = base.due();
+        assert!(base.interval.num_days() >= 3);
+
+        let empty = fsrs.next_card_balanced(Some(card), later, Grade::Good, |_| 0);
+        assert_eq!(empty, base);
+
+        let mut scheduled = vec![];
+        for _ in 0..2 {
+            let next = fsrs.next_card_balanced(Some(card), later, Grade::Good, |due| {
+                let preloaded = if due == base_due { 50 } else { 0 };
+                preloaded + scheduled.iter().filter(|&&d| d == due).count()
+            });
+            assert_eq!(next.memory_state(), base.memory_state());
+            scheduled.push(next.due());
+        }
+        assert_ne!(scheduled[0], base_due);
+        assert_ne!(scheduled[1], base_due);
+        assert_ne!(scheduled[0], scheduled[1]);
+        let range = fsrs
+            .parameters()
+            .fuzz_range(base.interval.num_days() as f64, card.interval.num_days());
+        for due in scheduled {
+            let days = (due - later).num_days();
+            assert!((range.0..=range.1).contains(&days));
+        }
+
+        let first = fsrs.next_card(None, now, Grade::Again);
+        assert_eq!(
+            fsrs.next_card_balanced(None, now, Grade::Again, |_| 100),
+            first
+        );
+    }
+
+    #[test]
+    fn preview() {
+        let fsrs = Fsrs::default();
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = fsrs.next_card(None, now, Grade::Good);
+        let later = now + card.interval;
+
+        let preview = fsrs.preview(Some(card), later);
+        assert_eq!(preview.good, fsrs.next_card(Some(card), later, Grade::Good));
+        assert_eq!(preview[Grade::Again].grade, Grade::Again);
+        assert!(preview.hard.interval < preview.good.interval);
+        assert!(preview.good.interval < preview.easy.interval);
+    }
+
+    #[test]
+    fn minimum_stability() {
+        let fsrs = Fsrs::new(ParametersBuilder::new().minimum_stability(0.2).build());
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let mut card = fsrs.next_card(None, now, Grade::Again);
+        for _ in 0..50 {
+            card = fsrs.next_card(Some(card), card.due(), Grade::Again);
+            assert!(card.stability >= 0.2);
+            assert!(card.interval >= Duration::days(1));
+        }
+        for _ in 0..50 {
+            card = fsrs.next_card(Some(card), card.reviewed_at, Grade::Again);
+        }
+        assert_eq!(card.stability, 0.2);
+        assert!(card.interval >= Duration::days(1));
+
+        assert_eq!(
+            ParametersBuilder::new().minimum_stability(0.0).try_build(),
+            Err(vec![ParamError::MinimumStability(0.0)])
+        );
+    }
+
+    #[test]
+    fn set_interval() {
+        let fsrs = Fsrs::default();
+        let p = &fsrs.parameters;
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = fsrs.next_card(None, now, Grade::Good);
+        let later = now + Duration::days(2);
+
+        let manual = fsrs.set_interval(card, 30, later);
+        assert_eq!(manual.reviewed_at, later);
+        assert_eq!(manual.interval, Duration::days(30));
+        assert_eq!(manual.difficulty, card.difficulty);
+        let retrievability = manual.retrievability(p, manual.due());
+        assert!((retrievability - p.retention).abs() < 1e-9);
+        assert_eq!(fsrs.reschedule(manual).interval, Duration::days(30));
+
+        assert_eq!(
+            fsrs.set_interval(card, 0, later).interval,
+            Duration::days(1)
+        );
+    }
+
+    #[test]
+    fn card_retention() {
+        let fsrs = Fsrs::default();
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let first = fsrs.next_card(None, now, Grade::Good);
+        let later = first.due();
+
+        let mut strict = first;
+        strict.set_retention(Some(0.95)).unwrap();
+        let default = fsrs.next_card(Some(first), later, Grade::Good);
+        let next = fsrs.next_card(Some(strict), later, Grade::Good);
+        assert_eq!(next.memory_state(), default.memory_state());
+        assert!(next.interval < default.interval);
+        assert_eq!(next.retention, Some(0.95));
+        assert!(fsrs.reschedule(strict).interval < first.interval);
+
+        for retention in [0.0, 1.0, f64::NAN] {
+            assert!(matches!(
+                strict.set_retention(Some(retention)),
+                Err(ParamError::Retention(_))
+            ));
+        }
+        assert_eq!(strict.retention, Some(0.95));
+        strict.set_retention(None).unwrap();
+        assert_eq!(fsrs.next_card(Some(strict), later, Grade::Good), default);
+
+        let invalid = Card {
+            retention: Some(1.5),
+            ..first
+        };
+        assert!(matches!(
+            fsrs.try_next_card(Some(invalid), later, Grade::Good),
+            Err(FsrsError::InvalidState(_))
+        ));
+    }
+
+    #[test]
+    fn interval_multipliers() {
+        let plain = Fsrs::default();
+        let adjusted = Fsrs::new(
+            ParametersBuilder::new()
+                .easy_bonus(1.3)
+                .hard_factor(0.8)
+                .build(),
+        );
+        let unchanged = Fsrs::new(
+            ParametersBuilder::new()
+                .easy_bonus(1.0)
+                .hard_factor(1.0)
+                .build(),
+        );
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = plain.next_card(None, now, Grade::Good);
+        let later = now + Duration::days(30);
+
+        let plain = plain.preview(Some(card), later);
+        let adjusted = adjusted.preview(Some(card), later);
+        assert_eq!(unchanged.preview(Some(card), later), plain);
+        assert_eq!(adjusted.again, plain.again);
+        assert_eq!(adjusted.good, plain.good);
+        // The multiplier applies before rounding to whole days
+        let p = Parameters::default();
+        let assert_interval = |card: Card, multiplier: f64| {
+            assert_eq!(
+                card.interval.num_days() as f64,
+                (p.inverse_forgetting_curve(card.stability, p.retention) * multiplier).round()
+            );
+        };
+        assert_interval(adjusted.hard, 0.8);
+        assert_interval(adjusted.easy, 1.3);
+        assert_eq!(adjusted.easy.stability, plain.easy.stability);
+    }
+
+    #[test]
+    fn graduating_interval() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let plain = Fsrs::default().preview(None, now);
+        assert_eq!(plain.good.interval.num_days(), 3);
+        assert_eq!(plain.easy.interval.num_days(), 16);
+
+        let fsrs = Fsrs::new(
+            ParametersBuilder::new()
+                .graduating_interval(5)
+                .easy_interval(20)
+                .build(),
+        );
+        let floored = fsrs.preview(None, now);
+        assert_eq!(floored.again, plain.again);
+        assert_eq!(floored.hard.interval.num_days(), 5);
+        assert_eq!(floored.good.interval.num_days(), 5);
+        assert_eq!(floored.easy.interval.num_days(), 20);
+        assert_eq!(floored.good.stability, plain.good.stability);
+
+        // Only the first review is affected
+        let later = now + Duration::days(1);
+        assert_eq!(
+            fsrs.next_card(Some(plain.again), later, Grade::Good),
+            Fsrs::default().next_card(Some(plain.again), later, Grade::Good)
+        );
+
+        let capped = Fsrs::new(
+            ParametersBuilder::new()
+                .easy_interval(20)
+                .maximum_interval(10)
+                .build(),
+        );
+        assert_eq!(
+            capped.next_card(None, now, Grade::Easy).interval.num_days(),
+            10
+        );
+    }
+
+    #[test]
+    fn sort_by_retrievability() {
+        let fsrs = Fsrs::default();
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let reviewed = now - Duration::days(10);
+        let card = |stability: f64| Card {
+            interval: Duration::days(1),
+            stability,
+            ..Card::from_memory_state(1.0, 5.0, reviewed)
+        };
+        let mut cards = [
+            card(f64::NAN),
+            card(50.0),
+            card(2.0),
+            card(-f64::NAN),
+            card(10.0),
+            card(2.0),
+        ];
+        cards[5].lapses = 1;
+        fsrs.sort_by_retrievability(&mut cards, now);
+
+        let stabilities: Vec<_> = cards[..4].iter().map(|card| card.stability).collect();
+        assert_eq!(stabilities, [2.0, 2.0, 10.0, 50.0]);
+        assert_eq!(cards[0].lapses, 0);
+        assert_eq!(cards[1].lapses, 1);
+        assert!(cards[4].stability.is_sign_positive() && cards[4].stability.is_nan());
+        assert!(cards[5].stability.is_sign_negative() && cards[5].stability.is_nan());
+    }
+
+    #[test]
+    fn next_intervals() {
+        let fsrs = Fsrs::new(
+            ParametersBuilder::new()
+                .enable_fuzz(true)
+                .maximum_interval(30)
+                .build(),
+        );
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        assert_eq!(Fsrs::default().next_intervals(None, now), [1, 1, 3, 16]);
+
+        let card = fsrs.next_card(None, now, Grade::Good);
+        let later = now + Duration::days(5);
+        let intervals = fsrs.next_intervals(Some(card), later);
+        for (grade, days) in Grade::all().into_iter().zip(intervals) {
+            let next = fsrs.next_card(Some(card), later, grade);
+            assert_eq!(days, next.interval.num_days());
+        }
+        assert!(intervals.iter().all(|&days| days <= 30));
+    }
+
+    #[test]
+    fn same_day_reviews() {
+        let fsrs = Fsrs::default();
+        let p = fsrs.parameters();
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let first = fsrs.next_card(None, now, Grade::Good);
+        let second = fsrs.next_card(Some(first), now + Duration::hours(1), Grade::Good);
+        let third = fsrs.next_card(Some(second), now + Duration::hours(2), Grade::Good);
+
+        assert_eq!(
+            second.stability,
+            p.short_term_stability(first.stability, Grade::Good)
+        );
+        assert!(second.stability > first.stability);
+        assert!(third.stability < 2.0 * first.stability);
+        assert_eq!(
+            p.projected_stability(&first, 0.0, Grade::Good),
+            second.stability
+        );
+
+        let forgotten = fsrs.next_card(Some(first), now + Duration::hours(1), Grade::Again);
+        assert!(forgotten.stability < first.stability);
+        assert!(forgotten.difficulty > first.difficulty);
+
+        // A day later, the long-term update applies again
+        let later = fsrs.next_card(Some(first), now + Duration::days(1), Grade::Good);
+        assert_eq!(
+            later.stability,
+            p.projected_stability(&first, 1.0, Grade::Good)
+        );
+        assert_ne!(later.stability, second.stability);
+    }
+
+    #[test]
+    fn maximum_interval() {
+        let fsrs = Fsrs::new(ParametersBuilder::new().maximum_interval(100).build());
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = Card {
+            reviewed_at: now,
+            interval: Duration::days(1000),
+            grade: Grade::Good,
+            stability: 1000.0,
+            difficulty: 5.0,
+            lapses: 0,
+            retention: None,
+        };
+
+        let preview = fsrs.preview(Some(card), now + card.interval);
+        assert_eq!(preview.good.interval.num_days(), 100);
+        assert_eq!(preview.easy.interval.num_days(), 100);
+    }
+
+    #[test]
+    fn reviewed() {
+        let fsrs = Fsrs::new(Parameters {
+            w: WEIGHTS.into(),
+            ..Default::default()
+        });
+
+        let mut card = None;
+        let mut now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let mut reviews = vec![];
+        for (i, grade) in TEST_GRADES.into_iter().enumerate() {
+            let next = fsrs.next_card(card, now, grade);
+            reviews.push((grade, now));
+            card = Some(next);
+            // Review some cards late to make sure elapsed time comes from the
+            // review timestamps rather than the scheduled intervals
+            now += next.interval + Duration::days(i as i64 % 3);
+        }
+
+        assert_eq!(fsrs.reviewed(reviews.clone()), card);
+        assert_eq!(
+            fsrs.reviewed(
+                reviews
+                    .into_iter()
+                    .map(|(grade, when)| Review::new(grade, when))
+            ),
+            card
+        );
+        assert_eq!(fsrs.reviewed(Vec::<Review>::new()), None);
+    }
+
+    #[test]
+    fn reviewed_out_of_order() {
+        let fsrs = Fsrs::new(Parameters {
+            w: WEIGHTS.into(),
+            ..Default::default()
+        });
+
+        let mut now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let mut reviews = vec![];
+        for (i, grade) in TEST_GRADES.into_iter().enumerate() {
+            reviews.push(Review::new(grade, now));
+            now += Duration::days(i as i64 * 5 + 1);
+        }
+        let in_order = fsrs.reviewed(reviews.clone());
+        assert_eq!(fsrs.try_reviewed(reviews.clone()), Ok(in_order));
+
+        let mut shuffled = reviews.clone();
+        shuffled.swap(2, 7);
+        shuffled.swap(0, 11);
+        assert_ne!(fsrs.reviewed(shuffled.clone()), in_order);
+        assert_eq!(fsrs.reviewed_sorted(shuffled.clone()), in_order);
+        assert_eq!(
+            fsrs.try_reviewed(shuffled),
+            Err(FsrsError::OutOfOrder(reviews[1]))
+        );
+    }
+
+    #[test]
+    fn clock() {
+        struct FixedClock(Time);
+
+        impl Clock for FixedClock {
+            fn now(&self) -> Time {
+                self.0
+            }
+        }
+
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let fsrs = Fsrs::with_clock(Parameters::default(), FixedClock(now));
+        let card = fsrs.schedule(None, Grade::Good);
+        assert_eq!(card.reviewed_at, now);
+        assert_eq!(card.due(), now + Duration::days(3));
+        assert_eq!(card, fsrs.next_card(None, now, Grade::Good));
+    }
+
+    #[test]
+    fn schedule_batch() {
+        let fsrs = Fsrs::default();
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let cards: Vec<_> = (0..100)
+            .map(|i| fsrs.next_card(None, now - Duration::days(i), TEST_GRADES[i as usize % 13]))
+            .collect();
+
+        let batch = fsrs.schedule_batch(&cards, now, Grade::Hard);
+        let expected: Vec<_> = cards
+            .iter()
+            .map(|&card| fsrs.next_card(Some(card), now, Grade::Hard))
+            .collect();
+        assert_eq!(batch, expected);
+    }
+
+    #[test]
+    fn reschedule() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = Fsrs::default().next_card(None, now, Grade::Easy);
+
+        let fsrs = Fsrs::new(ParametersBuilder::new().retention(0.8).build());
+        let rescheduled = fsrs.reschedule(card);
+        assert_eq!(rescheduled.stability, card.stability);
+        assert_eq!(rescheduled.difficulty, card.difficulty);
+        assert_eq!(rescheduled.reviewed_at, card.reviewed_at);
+        assert!(rescheduled.interval > card.interval);
+        assert_eq!(
+            rescheduled.interval,
+            fsrs.next_card(None, now, Grade::Easy).interval
+        );
+    }
+
+    #[test]
+    fn enormous_stability() {
+        let fsrs = Fsrs::new(ParametersBuilder::new().maximum_interval(i64::MAX).build());
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        for stability in [1e300, f64::INFINITY] {
+            let card = fsrs.reschedule(Card::from_memory_state(stability, 5.0, now));
+            assert_eq!(card.interval.num_days(), Fsrs::MAX_INTERVAL_DAYS);
+            assert!(card.due() > now);
+        }
+
+        let card = fsrs.reschedule(Card::from_memory_state(1.0, 5.0, now));
+        let card = fsrs.reschedule(Card {
+            stability: f64::NAN,
+            ..card
+        });
+        assert_eq!(card.interval.num_days(), 1);
+    }
 }
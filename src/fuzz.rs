@@ -0,0 +1,113 @@
+use crate::{rng::Rng, Parameters, Time};
+
+/// Portion of an interval within which fuzz may move a review
+struct FuzzRange {
+    start: f64,
+    end: f64,
+    factor: f64,
+}
+
+const FUZZ_RANGES: [FuzzRange; 3] = [
+    FuzzRange {
+        start: 2.5,
+        end: 7.0,
+        factor: 0.15,
+    },
+    FuzzRange {
+        start: 7.0,
+        end: 20.0,
+        factor: 0.1,
+    },
+    FuzzRange {
+        start: 20.0,
+        end: f64::INFINITY,
+        factor: 0.05,
+    },
+];
+
+impl Parameters {
+    /// Inclusive range of days that fuzz may choose for an interval
+    pub(crate) fn fuzz_range(&self, interval: f64, elapsed_days: i64) -> (i64, i64) {
+        let delta = FUZZ_RANGES.iter().fold(1.0, |delta, range| {
+            delta + range.factor * (interval.min(range.end) - range.start).max(0.0)
+        });
+        let interval = interval.min(self.maximum_interval_days() as f64);
+        let mut min = ((interval - delta).round() as i64).max(2);
+        let max = ((interval + delta).round() as i64).min(self.maximum_interval_days());
+        if interval > elapsed_days as f64 {
+            min = min.max(elapsed_days + 1);
+        }
+        (min.min(max), max)
+    }
+
+    /// Randomly adjust an interval in days when fuzz is enabled
+    ///
+    /// The adjustment is deterministic for a given seed and review.
+    pub(crate) fn fuzz_interval(
+        &self,
+        interval: f64,
+        elapsed_days: i64,
+        reviewed_at: Time,
+        stability: f64,
+    ) -> f64 {
+        if !self.enable_fuzz || interval < 2.5 {
+            return interval;
+        }
+
+        let (min, max) = self.fuzz_range(interval, elapsed_days);
+        let seed = self.fuzz_seed ^ (reviewed_at.timestamp_millis() as u64) ^ stability.to_bits();
+        let offset = Rng::new(seed).next_f64() * (max - min + 1) as f64;
+        (min as f64 + offset).floor().min(max as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fsrs::tests::string_to_utc, Duration, Fsrs, Grade, ParametersBuilder};
+
+    #[test]
+    fn fuzz() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let plain = Fsrs::new(ParametersBuilder::new().build());
+        let fuzzed = ParametersBuilder::new()
+            .enable_fuzz(true)
+            .fuzz_seed(7)
+            .build();
+
+        let mut card = plain.next_card(None, now, Grade::Easy);
+        for _ in 0..5 {
+            let later = card.reviewed_at + card.interval;
+            let next = plain.next_card(Some(card), later, Grade::Good);
+            let interval = next.interval.num_days() as f64;
+            let (min, max) = fuzzed.fuzz_range(interval, card.interval.num_days());
+            let fuzzed_card = Fsrs::new(fuzzed).next_card(Some(card), later, Grade::Good);
+            let days = fuzzed_card.interval.num_days();
+            assert!((min..=max).contains(&days));
+            assert_eq!(
+                fuzzed_card,
+                Fsrs::new(fuzzed).next_card(Some(card), later, Grade::Good)
+            );
+            card = next;
+        }
+
+        let seeds: Vec<_> = (0..20)
+            .map(|seed| {
+                let parameters = ParametersBuilder::new()
+                    .enable_fuzz(true)
+                    .fuzz_seed(seed)
+                    .build();
+                Fsrs::new(parameters)
+                    .next_card(Some(card), now + Duration::days(400), Grade::Good)
+                    .interval
+            })
+            .collect();
+        assert!(seeds.iter().any(|&interval| interval != seeds[0]));
+    }
+
+    #[test]
+    fn short_intervals_are_not_fuzzed() {
+        let parameters = ParametersBuilder::new().enable_fuzz(true).build();
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        assert_eq!(parameters.fuzz_interval(2.0, 0, now, 2.0), 2.0);
+    }
+}
@@ -1,17 +1,82 @@
 mod cards;
+pub use cards::Cards;
 
 mod fsrs;
-pub use fsrs::{Fsrs, Review};
+pub use fsrs::{Fsrs, FsrsError, Review};
+
+mod session;
+pub use session::Session;
+
+mod explain;
+pub use explain::Explanation;
+
+mod review_log;
+pub use review_log::ReviewLog;
+
+mod clock;
+pub use clock::{Clock, SystemClock};
 
 mod card;
-pub use card::{Card, Grade};
+pub use card::{Card, Grade, GradeValueError, ParseGradeError};
+
+mod card_builder;
+pub use card_builder::CardBuilder;
 
 mod parameters;
-pub use parameters::Parameters;
+pub use parameters::{CurveKind, IntervalRounding, ParamError, Parameters, Version};
 
 mod parameters_builder;
 pub use parameters_builder::ParametersBuilder;
 
+pub mod weights;
+
+mod diff;
+pub use diff::ParametersDiff;
+
+mod table;
+pub use table::IntervalRow;
+
+mod optimizer;
+pub use optimizer::{Optimizer, ReviewHistory};
+
+mod evaluation;
+pub use evaluation::CalibrationBin;
+
+pub mod bench;
+
+mod simulate;
+pub use simulate::{simulate, simulate_student, SimulationResult, StudentResult};
+
+pub mod stats;
+
+mod queue;
+pub use queue::{build_queue, Queue};
+
+mod fuzz;
+
+mod day_cutoff;
+
+mod format;
+pub use format::format_interval;
+
+#[cfg(feature = "postcard")]
+mod binary;
+#[cfg(feature = "postcard")]
+pub use binary::PostcardError;
+
+#[cfg(feature = "csv")]
+mod export;
+#[cfg(feature = "csv")]
+pub use export::{export_reviews, import_reviews};
+
+mod rng;
+
+#[cfg(feature = "compat")]
+pub mod compat;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
 #[doc = include_str!("../README.md")]
 mod readme {}
 
@@ -0,0 +1,444 @@
+use crate::{parameters::WeightsV6, weights, Duration, Grade, Parameters, Review};
+use std::ops::ControlFlow;
+
+/// The reviews of a single card in chronological order
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ReviewHistory {
+    /// Reviews of the card, oldest first
+    pub reviews: Vec<Review>,
+}
+
+impl ReviewHistory {
+    /// Create a new review history
+    pub fn new(reviews: Vec<Review>) -> Self {
+        Self { reviews }
+    }
+
+    /// Add the reviews of another history of the same card, such as from
+    /// another device
+    ///
+    /// The reviews are sorted by time and exact duplicates are dropped.
+    pub fn merge(&mut self, other: ReviewHistory) {
+        self.merge_within(other, Duration::zero());
+    }
+
+    /// Add the reviews of another history like [ReviewHistory::merge],
+    /// also treating reviews with the same grade as duplicates when they are
+    /// close together
+    ///
+    /// Each review that is kept starts a group, and later reviews with the
+    /// same grade no more than `tolerance` after it are dropped, even if
+    /// reviews with other grades come between them.
+    pub fn merge_within(&mut self, other: ReviewHistory, tolerance: Duration) {
+        let mut reviews = std::mem::take(&mut self.reviews);
+        reviews.extend(other.reviews);
+        reviews.sort_by_key(|review| (review.when, review.grade));
+        for review in reviews {
+            let duplicate = self
+                .reviews
+                .iter()
+                .rev()
+                .take_while(|kept| review.when - kept.when <= tolerance)
+                .any(|kept| kept.grade == review.grade);
+            if !duplicate {
+                self.reviews.push(review);
+            }
+        }
+    }
+}
+
+impl FromIterator<Review> for ReviewHistory {
+    fn from_iter<T: IntoIterator<Item = Review>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+/// Fits FSRS weights to a collection of review histories
+///
+/// Training minimizes [Parameters::log_loss].
+#[derive(Debug, Clone, PartialEq, PartialOrd, Default)]
+pub struct Optimizer {
+    histories: Vec<ReviewHistory>,
+}
+
+impl Optimizer {
+    const EPOCHS: usize = 200;
+    const LEARNING_RATE: f64 = 0.04;
+    const BETA_1: f64 = 0.9;
+    const BETA_2: f64 = 0.999;
+    const EPSILON: f64 = 1e-8;
+    const STEP: f64 = 1e-5;
+    /// Smallest decrease in loss that counts as an improvement
+    const TOLERANCE: f64 = 1e-7;
+    /// Number of epochs without improvement before training stops
+    const PATIENCE: usize = 10;
+
+    /// Lower and upper bounds for each weight during training
+    const BOUNDS: [(f64, f64); 21] = [
+        (0.01, 100.0),
+        (0.01, 100.0),
+        (0.01, 100.0),
+        (0.01, 100.0),
+        (1.0, 10.0),
+        (0.001, 4.0),
+        (0.001, 4.0),
+        (0.001, 0.75),
+        (0.0, 4.5),
+        (0.0, 0.8),
+        (0.001, 3.5),
+        (0.001, 5.0),
+        (0.001, 0.25),
+        (0.001, 0.9),
+        (0.0, 4.0),
+        (0.0, 1.0),
+        (1.0, 6.0),
+        (0.0, 2.0),
+        (0.0, 2.0),
+        (0.0, 0.8),
+        (0.1, 0.8),
+    ];
+
+    /// Create a new optimizer for the given review histories
+    pub fn new(histories: Vec<ReviewHistory>) -> Self {
+        Self { histories }
+    }
+
+    /// Train the weights, starting from the given parameters
+    ///
+    /// Parameters other than the weights are left unchanged, including the
+    /// [Version](crate::Version).
+    pub fn train(&self, initial: Parameters) -> Parameters {
+        self.train_with(initial, |_, _| ControlFlow::Continue(()))
+    }
+
+    /// Train the weights like [Optimizer::train], reporting progress
+    ///
+    /// After each epoch, `progress` receives the epoch number, starting from
+    /// 1, and the loss. Training ends when it returns [ControlFlow::Break],
+    /// when the loss stops improving, or after 200 epochs. The parameters
+    /// with the lowest loss seen are returned, which may be the initial ones.
+    pub fn train_with(
+        &self,
+        initial: Parameters,
+        mut progress: impl FnMut(usize, f64) -> ControlFlow<()>,
+    ) -> Parameters {
+        let mut parameters = initial;
+        let mut first_moment: WeightsV6 = [0.0; 21];
+        let mut second_moment: WeightsV6 = [0.0; 21];
+        let mut best = (initial.log_loss(&self.histories), initial);
+        let mut stale = 0;
+
+        for epoch in 1..=Self::EPOCHS {
+            let gradient = self.gradient(parameters);
+            let correction_1 = 1.0 - Self::BETA_1.powi(epoch as i32);
+            let correction_2 = 1.0 - Self::BETA_2.powi(epoch as i32);
+            let count = parameters.version().weight_count();
+            for (i, g) in gradient.into_iter().take(count).enumerate() {
+                first_moment[i] = Self::BETA_1 * first_moment[i] + (1.0 - Self::BETA_1) * g;
+                second_moment[i] = Self::BETA_2 * second_moment[i] + (1.0 - Self::BETA_2) * g * g;
+                let m = first_moment[i] / correction_1;
+                let v = second_moment[i] / correction_2;
+                let (low, high) = Self::BOUNDS[i];
+                let w = parameters.w[i] - Self::LEARNING_RATE * m / (v.sqrt() + Self::EPSILON);
+                parameters.w.set(i, w.clamp(low, high));
+            }
+
+            let loss = parameters.log_loss(&self.histories);
+            if loss < best.0 - Self::TOLERANCE {
+                stale = 0;
+            } else {
+                stale += 1;
+            }
+            if loss < best.0 {
+                best = (loss, parameters);
+            }
+            if progress(epoch, loss).is_break() || stale >= Self::PATIENCE {
+                break;
+            }
+        }
+
+        best.1
+    }
+
+    /// Estimate the initial stability for each grade from the first two
+    /// reviews of each card, leaving the other parameters unchanged
+    ///
+    /// For each grade of the first review, this finds the stability whose
+    /// forgetting curve best predicts recall at the second review. It is much
+    /// faster than [Optimizer::train] and makes a good starting point for it.
+    /// Grades that no card started with keep their initial stability, as do
+    /// cards whose second review was on the same day as the first.
+    pub fn pretrain(&self, base: Parameters) -> Parameters {
+        let mut samples: [Vec<(f64, bool)>; 4] = Default::default();
+        for history in &self.histories {
+            if let [first, second, ..] = history.reviews.as_slice() {
+                let days = second.when.signed_duration_since(first.when).num_days();
+                if days > 0 {
+                    samples[first.grade.index() as usize]
+                        .push((days as f64, second.grade != Grade::Again));
+                }
+            }
+        }
+
+        let mut parameters = base;
+        for (index, samples) in weights::INITIAL_STABILITY.into_iter().zip(samples) {
+            if samples.is_empty() {
+                continue;
+            }
+            let loss = |stability: f64| -> f64 {
+                samples
+                    .iter()
+                    .map(|&(days, recalled)| {
+                        let r = parameters
+                            .forgetting_curve(days, stability)
+                            .clamp(1e-9, 1.0 - 1e-9);
+                        if recalled {
+                            -r.ln()
+                        } else {
+                            -(1.0 - r).ln()
+                        }
+                    })
+                    .sum()
+            };
+            let (low, high) = Self::BOUNDS[index];
+            parameters.w.set(index, golden_section(low, high, loss));
+        }
+        parameters
+    }
+
+    /// Central difference approximation of the loss gradient
+    fn gradient(&self, parameters: Parameters) -> WeightsV6 {
+        let mut gradient = [0.0; 21];
+        let count = parameters.version().weight_count();
+        for (i, g) in gradient.iter_mut().take(count).enumerate() {
+            let step = Self::STEP * parameters.w[i].abs().max(1.0);
+            let mut above = parameters;
+            above.w.set(i, parameters.w[i] + step);
+            let mut below = parameters;
+            below.w.set(i, parameters.w[i] - step);
+            *g = (above.log_loss(&self.histories) - below.log_loss(&self.histories)) / (2.0 * step);
+        }
+        gradient
+    }
+}
+
+/// Minimize a unimodal function of a positive value, searching evenly in
+/// log space between the bounds
+fn golden_section(low: f64, high: f64, f: impl Fn(f64) -> f64) -> f64 {
+    const RATIO: f64 = 0.618_033_988_749_894_8;
+    let (mut a, mut b) = (low.ln(), high.ln());
+    for _ in 0..100 {
+        let c = b - RATIO * (b - a);
+        let d = a + RATIO * (b - a);
+        if f(c.exp()) < f(d.exp()) {
+            b = d;
+        } else {
+            a = c;
+        }
+    }
+    ((a + b) / 2.0).exp()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::{Optimizer, ReviewHistory};
+    use crate::{
+        fsrs::tests::{string_to_utc, WEIGHTS},
+        rng::Rng,
+        Card, Duration, Fsrs, Grade, Parameters, ParametersBuilder, Review,
+    };
+    use std::ops::ControlFlow;
+
+    /// Review histories where recall follows the given parameters
+    pub fn synthetic_histories(parameters: Parameters, cards: usize) -> Vec<ReviewHistory> {
+        let fsrs = Fsrs::new(parameters);
+        let mut rng = Rng::new(0x2545_f491_4f6c_dd1d);
+        let start = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        (0..cards)
+            .map(|_| {
+                let mut now = start;
+                let mut card: Option<Card> = None;
+                let mut reviews = vec![];
+                for _ in 0..8 {
+                    let grade = match card {
+                        Some(c) if rng.next_f64() >= c.retrievability(&parameters, now) => {
+                            Grade::Again
+                        }
+                        _ => Grade::Good,
+                    };
+                    reviews.push(Review::new(grade, now));
+                    let next = fsrs.next_card(card, now, grade);
+                    let days = next.interval.num_days() as f64 * (0.5 + 1.5 * rng.next_f64());
+                    now += Duration::days(days.round().max(1.0) as i64);
+                    card = Some(next);
+                }
+                ReviewHistory::new(reviews)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merge() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let at = |days, grade| Review::new(grade, now + Duration::days(days));
+        let phone = ReviewHistory::new(vec![
+            at(0, Grade::Good),
+            at(3, Grade::Good),
+            at(10, Grade::Again),
+        ]);
+        let laptop = ReviewHistory::new(vec![
+            at(3, Grade::Good),
+            at(0, Grade::Good),
+            at(5, Grade::Hard),
+            at(3, Grade::Easy),
+        ]);
+
+        let mut merged = phone.clone();
+        merged.merge(laptop.clone());
+        assert_eq!(
+            merged.reviews,
+            [
+                at(0, Grade::Good),
+                at(3, Grade::Good),
+                at(3, Grade::Easy),
+                at(5, Grade::Hard),
+                at(10, Grade::Again),
+            ]
+        );
+
+        let mut synced = phone;
+        let late = Review::new(
+            Grade::Again,
+            now + Duration::days(10) + Duration::seconds(30),
+        );
+        synced.merge_within(ReviewHistory::new(vec![late]), Duration::minutes(1));
+        assert_eq!(synced.reviews.len(), 3);
+        synced.merge(ReviewHistory::new(vec![late]));
+        assert_eq!(synced.reviews.len(), 4);
+        assert!(synced.reviews.windows(2).all(|w| w[0].when <= w[1].when));
+
+        // Groups start at the first review and skip over other grades
+        let at = |seconds, grade| Review::new(grade, now + Duration::seconds(seconds));
+        let mut history = ReviewHistory::new(vec![at(60, Grade::Good), at(0, Grade::Good)]);
+        history.merge_within(
+            ReviewHistory::new(vec![
+                at(30, Grade::Again),
+                at(30, Grade::Good),
+                at(90, Grade::Good),
+                at(60, Grade::Again),
+            ]),
+            Duration::minutes(1),
+        );
+        assert_eq!(
+            history.reviews,
+            [
+                at(0, Grade::Good),
+                at(30, Grade::Again),
+                at(90, Grade::Good)
+            ]
+        );
+    }
+
+    #[test]
+    fn pretrain() {
+        let truth = [0.5, 2.0, 6.0, 20.0];
+        let parameters = Parameters::default();
+        let start = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let mut rng = Rng::new(17);
+        let mut histories = vec![];
+        for (grade, stability) in Grade::all().into_iter().zip(truth) {
+            for _ in 0..2000 {
+                let days = 1 + (rng.next_f64() * 30.0) as i64;
+                let r = parameters.forgetting_curve(days as f64, stability);
+                let second = if rng.next_f64() < r {
+                    Grade::Good
+                } else {
+                    Grade::Again
+                };
+                histories.push(ReviewHistory::new(vec![
+                    Review::new(grade, start),
+                    Review::new(second, start + Duration::days(days)),
+                ]));
+            }
+        }
+        // Same-day and single reviews carry no information
+        histories.push(ReviewHistory::new(vec![
+            Review::new(Grade::Easy, start),
+            Review::new(Grade::Again, start),
+        ]));
+        histories.push(ReviewHistory::new(vec![Review::new(Grade::Easy, start)]));
+
+        let pretrained = Optimizer::new(histories).pretrain(parameters);
+        for (i, stability) in truth.into_iter().enumerate() {
+            let estimate = pretrained.w[i];
+            assert!((estimate / stability - 1.0).abs() < 0.2, "{i}: {estimate}");
+        }
+        assert_eq!(pretrained.weights()[4..], parameters.weights()[4..]);
+
+        let easy_only = vec![ReviewHistory::new(vec![
+            Review::new(Grade::Easy, start),
+            Review::new(Grade::Good, start + Duration::days(10)),
+        ])];
+        let pretrained = Optimizer::new(easy_only).pretrain(parameters);
+        assert_eq!(pretrained.weights()[..3], parameters.weights()[..3]);
+        assert!(pretrained.w[3] > parameters.w[3]);
+    }
+
+    #[test]
+    fn train_with_progress() {
+        let histories = synthetic_histories(Parameters::default(), 30);
+        let optimizer = Optimizer::new(histories.clone());
+        let initial = ParametersBuilder::new().retention(0.8).build();
+
+        let mut epochs = vec![];
+        let trained = optimizer.train_with(initial, |epoch, loss| {
+            epochs.push((epoch, loss));
+            ControlFlow::Continue(())
+        });
+        assert!(!epochs.is_empty());
+        assert!(epochs
+            .iter()
+            .enumerate()
+            .all(|(i, &(epoch, _))| epoch == i + 1));
+        let best = epochs
+            .iter()
+            .map(|&(_, loss)| loss)
+            .fold(f64::INFINITY, f64::min);
+        assert_eq!(
+            trained.log_loss(&histories),
+            best.min(initial.log_loss(&histories))
+        );
+        assert_eq!(trained.retention, 0.8);
+
+        let mut calls = 0;
+        optimizer.train_with(initial, |epoch, _| {
+            calls += 1;
+            if epoch == 3 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn train() {
+        let mut w = WEIGHTS;
+        w[2] = 8.0;
+        let truth = Parameters {
+            w: w.into(),
+            ..Default::default()
+        };
+        let histories = synthetic_histories(truth, 150);
+        let initial = Parameters::default();
+
+        let trained = Optimizer::new(histories.clone()).train(initial);
+        let initial_loss = initial.log_loss(&histories);
+        let trained_loss = trained.log_loss(&histories);
+        let true_loss = truth.log_loss(&histories);
+        assert!(trained_loss < initial_loss);
+        assert!(trained_loss < true_loss + 0.01);
+        assert!((trained.w[2] - w[2]).abs() < (initial.w[2] - w[2]).abs());
+    }
+}
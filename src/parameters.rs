@@ -1,14 +1,108 @@
-use crate::{Grade, ParametersBuilder};
+#[cfg(feature = "chrono-tz")]
+use crate::day_cutoff::DueTime;
+use crate::{
+    day_cutoff::DayCutoff,
+    weights::{
+        DECAY as W_DECAY, DIFFICULTY_DELTA, EASY_BONUS, FORGET_DIFFICULTY, FORGET_RETRIEVABILITY,
+        FORGET_STABILITY, FORGET_STABILITY_EXPONENT, HARD_PENALTY, INITIAL_DIFFICULTY,
+        INITIAL_DIFFICULTY_GRADE, INITIAL_STABILITY, MEAN_REVERSION, RECALL_RETRIEVABILITY,
+        RECALL_STABILITY, RECALL_STABILITY_DECAY, SHORT_TERM_GRADE, SHORT_TERM_STABILITY,
+        SHORT_TERM_STABILITY_DECAY,
+    },
+    Card, Grade, ParametersBuilder,
+};
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    ops::Index,
+};
 
-/// FSRS algorithm weights
+/// FSRS-5 algorithm weights
 pub type Weights = [f64; 19];
 
+/// FSRS-6 algorithm weights
+pub type WeightsV6 = [f64; 21];
+
+/// FSRS model generation, determined by the number of weights
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Version {
+    /// 19 weights with a fixed forgetting curve decay
+    Fsrs5,
+    /// 21 weights with a learnable forgetting curve decay
+    Fsrs6,
+}
+
+impl Version {
+    /// Number of weights used by this version
+    pub const fn weight_count(self) -> usize {
+        match self {
+            Version::Fsrs5 => 19,
+            Version::Fsrs6 => 21,
+        }
+    }
+}
+
+/// Shape of the forgetting curve
+///
+/// Both curves give 90% retrievability when the elapsed days equal the
+/// stability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum CurveKind {
+    /// The exponential curve of FSRS v3, `0.9^(t / S)`
+    Exponential,
+    /// The power curve used from FSRS-4.5 onwards
+    #[default]
+    Power,
+}
+
+/// How computed intervals are rounded to whole days
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum IntervalRounding {
+    /// Round down
+    Floor,
+    /// Round to the nearest day, with halves rounded up
+    #[default]
+    Round,
+    /// Round up
+    Ceil,
+}
+
+impl IntervalRounding {
+    pub(crate) fn apply(self, days: f64) -> f64 {
+        match self {
+            IntervalRounding::Floor => days.floor(),
+            IntervalRounding::Round => days.round(),
+            IntervalRounding::Ceil => days.ceil(),
+        }
+    }
+}
+
 /// FSRS algorithm parameters
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Parameters {
-    pub(crate) w: Weights,
+    pub(crate) w: WeightSet,
     pub(crate) retention: f64,
-    pub(crate) maximum_interval: i32,
+    pub(crate) maximum_interval: i64,
+    pub(crate) enable_fuzz: bool,
+    pub(crate) fuzz_seed: u64,
+    pub(crate) day_cutoff: Option<DayCutoff>,
+    pub(crate) leech_threshold: u32,
+    pub(crate) easy_bonus: f64,
+    pub(crate) hard_factor: f64,
+    pub(crate) graduating_interval: i64,
+    pub(crate) easy_interval: i64,
+    pub(crate) curve: CurveKind,
+    pub(crate) minimum_stability: f64,
+    pub(crate) interval_rounding: IntervalRounding,
+    pub(crate) lapse_factor: f64,
+    #[cfg(feature = "chrono-tz")]
+    pub(crate) due_time: Option<DueTime>,
 }
 
 impl Parameters {
@@ -19,30 +113,279 @@ impl Parameters {
         1.01925, 1.9395, 0.11, 0.29605, 2.2698, 0.2315, 2.9898, 0.51655, 0.6621,
     ];
 
-    pub(crate) fn forgetting_curve(&self, elapsed_days: f64, stability: f64) -> f64 {
-        (1.0 + Self::FACTOR * elapsed_days / stability).powf(Self::DECAY)
+    /// Create parameters from 17, 19, or 21 weights
+    ///
+    /// FSRS-4.5 sets of 17 weights are converted to the FSRS-5 layout,
+    /// remapping the initial difficulty weights and leaving the two new
+    /// short-term weights at zero. Other parameters take their default values.
+    pub fn from_weights(weights: &[f64]) -> Result<Self, ParamError> {
+        Ok(Self {
+            w: weights.try_into()?,
+            ..Default::default()
+        })
+    }
+
+    /// Parse weights from the comma-separated list shown in Anki's deck
+    /// options, such as `[0.40255, 1.18385, ...]`
+    ///
+    /// The surrounding brackets are optional and whitespace is ignored. The
+    /// weights are then converted as in [Parameters::from_weights].
+    pub fn from_anki_string(s: &str) -> Result<Self, ParamError> {
+        let s = s.trim();
+        let s = s
+            .strip_prefix('[')
+            .and_then(|s| s.strip_suffix(']'))
+            .unwrap_or(s);
+        let weights = s
+            .split(',')
+            .enumerate()
+            .map(|(i, w)| match w.trim().parse::<f64>() {
+                Ok(w) if w.is_finite() => Ok(w),
+                Ok(_) => Err(ParamError::NonFiniteWeight(i)),
+                Err(_) => Err(ParamError::InvalidWeight(i)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_weights(&weights)
+    }
+
+    /// Format the weights as the bracketed list Anki stores in its deck
+    /// config
+    pub fn to_anki_string(&self) -> String {
+        let weights: Vec<_> = self.weights().iter().map(f64::to_string).collect();
+        format!("[{}]", weights.join(", "))
+    }
+
+    /// Check that the parameters produce meaningful schedules
+    ///
+    /// Returns every problem found rather than stopping at the first.
+    pub fn validate(&self) -> Result<(), Vec<ParamError>> {
+        let mut errors = vec![];
+        for (i, &w) in self.weights().iter().enumerate() {
+            if !w.is_finite() {
+                errors.push(ParamError::NonFiniteWeight(i));
+            } else if w < 0.0
+                && (INITIAL_STABILITY.contains(&i) || (RECALL_STABILITY..=EASY_BONUS).contains(&i))
+            {
+                errors.push(ParamError::NegativeWeight(i));
+            }
+        }
+        if !(1.0..=10.0).contains(&self.w[INITIAL_DIFFICULTY]) {
+            errors.push(ParamError::InitialDifficulty(self.w[INITIAL_DIFFICULTY]));
+        }
+        if !(0.0..=1.0).contains(&self.w[MEAN_REVERSION]) {
+            errors.push(ParamError::MeanReversion(self.w[MEAN_REVERSION]));
+        }
+        if self.version() == Version::Fsrs6 && self.w[W_DECAY] <= 0.0 {
+            errors.push(ParamError::Decay(self.w[W_DECAY]));
+        }
+        if !(self.retention > 0.0 && self.retention < 1.0) {
+            errors.push(ParamError::Retention(self.retention));
+        }
+        if self.maximum_interval < 1 {
+            errors.push(ParamError::MaximumInterval(self.maximum_interval));
+        }
+        if !(self.easy_bonus >= 1.0 && self.easy_bonus.is_finite()) {
+            errors.push(ParamError::EasyBonus(self.easy_bonus));
+        }
+        if !(self.hard_factor > 0.0 && self.hard_factor <= 1.0) {
+            errors.push(ParamError::HardFactor(self.hard_factor));
+        }
+        if self.graduating_interval < 1 {
+            errors.push(ParamError::GraduatingInterval(self.graduating_interval));
+        }
+        if self.easy_interval < 1 {
+            errors.push(ParamError::EasyInterval(self.easy_interval));
+        }
+        if !(self.lapse_factor > 0.0 && self.lapse_factor.is_finite()) {
+            errors.push(ParamError::LapseFactor(self.lapse_factor));
+        }
+        if !(self.minimum_stability > 0.0 && self.minimum_stability.is_finite()) {
+            errors.push(ParamError::MinimumStability(self.minimum_stability));
+        }
+        if let Some(cutoff) = self.day_cutoff {
+            if cutoff.hour >= 24 {
+                errors.push(ParamError::DayCutoffHour(cutoff.hour));
+            }
+        }
+        #[cfg(feature = "chrono-tz")]
+        if let Some(time) = self.due_time {
+            if time.hour >= 24 || time.minute >= 60 {
+                errors.push(ParamError::TimeOfDay(time.hour, time.minute));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// A copy of these parameters with changes made by the builder, checked
+    /// with [Parameters::validate]
+    ///
+    /// The builder starts with the values of these parameters.
+    pub fn with(
+        self,
+        f: impl FnOnce(ParametersBuilder) -> ParametersBuilder,
+    ) -> Result<Self, Vec<ParamError>> {
+        f(self.into()).try_build()
+    }
+
+    /// A copy of these parameters with a different desired retention
+    pub fn clone_with_retention(&self, retention: f64) -> Result<Self, Vec<ParamError>> {
+        self.with(|builder| builder.retention(retention))
+    }
+
+    /// A copy of these parameters with a different maximum interval
+    pub fn clone_with_maximum_interval(
+        &self,
+        maximum_interval: i64,
+    ) -> Result<Self, Vec<ParamError>> {
+        self.with(|builder| builder.maximum_interval(maximum_interval))
+    }
+
+    /// The FSRS model used by these parameters
+    pub fn version(&self) -> Version {
+        self.w.version
+    }
+
+    /// The algorithm weights
+    pub fn weights(&self) -> &[f64] {
+        self.w.as_slice()
+    }
+
+    /// Forgetting curve exponent
+    pub(crate) fn decay(&self) -> f64 {
+        match self.w.version {
+            Version::Fsrs5 => Self::DECAY,
+            Version::Fsrs6 => -self.w[W_DECAY],
+        }
+    }
+
+    /// Forgetting curve scale, chosen so that retrievability is 90% when the
+    /// elapsed days equal the stability
+    ///
+    /// Cached with the weights since it only changes with the decay.
+    pub(crate) fn factor(&self) -> f64 {
+        self.w.factor
+    }
+
+    /// Probability of recalling a memory with the given stability after the
+    /// given number of days
+    ///
+    /// Negative elapsed days, such as when studying ahead, are treated as zero.
+    pub fn forgetting_curve(&self, elapsed_days: f64, stability: f64) -> f64 {
+        let elapsed_days = elapsed_days.max(0.0);
+        match self.curve {
+            CurveKind::Power => (1.0 + self.factor() * elapsed_days / stability).powf(self.decay()),
+            CurveKind::Exponential => 0.9f64.powf(elapsed_days / stability),
+        }
+    }
+
+    /// Retrievability of the card when it becomes due
+    ///
+    /// For a review on the due date, this is the desired retention up to the
+    /// rounding of the interval to whole days, unless the interval was
+    /// limited by fuzz, the minimum or maximum interval, or a multiplier.
+    pub fn retrievability_at_due(&self, card: &Card) -> f64 {
+        card.retrievability(self, card.due())
     }
 
     pub(crate) fn init_difficulty(&self, grade: Grade) -> f64 {
         let grade_int: i32 = grade as i32;
 
-        (self.w[4] - f64::exp(self.w[5] * (grade_int as f64 - 1.0)) + 1.0).clamp(1.0, 10.0)
+        (self.w[INITIAL_DIFFICULTY]
+            - f64::exp(self.w[INITIAL_DIFFICULTY_GRADE] * (grade_int as f64 - 1.0))
+            + 1.0)
+            .clamp(1.0, 10.0)
     }
 
     pub(crate) fn init_stability(&self, grade: Grade) -> f64 {
-        let grade_int: i32 = grade as i32;
-        self.w[(grade_int - 1) as usize].max(0.1)
+        self.w[INITIAL_STABILITY[grade.index() as usize]].max(0.1)
+    }
+
+    /// The maximum interval in days, treating values less than a day as one
+    /// day so that unvalidated parameters still schedule cards
+    pub(crate) fn maximum_interval_days(&self) -> i64 {
+        self.maximum_interval.max(1)
     }
 
     pub(crate) fn next_interval(&self, stability: f64) -> f64 {
-        (stability / Self::FACTOR * (self.retention.powf(1.0 / Self::DECAY) - 1.0))
-            .round()
-            .clamp(1.0, self.maximum_interval as f64)
+        self.next_interval_for_retention(stability, self.retention)
+    }
+
+    /// The next interval with the easy bonus or hard factor applied
+    pub(crate) fn graded_interval(&self, stability: f64, grade: Grade) -> f64 {
+        self.interval_rounding
+            .apply(self.unrounded_interval(stability, grade))
+            .clamp(1.0, self.maximum_interval_days() as f64)
+    }
+
+    /// The fractional next interval with the easy bonus or hard factor
+    /// applied, before rounding and the maximum interval
+    pub(crate) fn unrounded_interval(&self, stability: f64, grade: Grade) -> f64 {
+        let multiplier = match grade {
+            Grade::Hard => self.hard_factor,
+            Grade::Easy => self.easy_bonus,
+            Grade::Again | Grade::Good => 1.0,
+        };
+        self.inverse_forgetting_curve(stability, self.retention) * multiplier
+    }
+
+    /// Number of days until a memory with the given stability decays to the
+    /// given retrievability
+    ///
+    /// The result is rounded to whole days by the
+    /// [IntervalRounding] and clamped to the maximum interval.
+    pub fn next_interval_for_retention(&self, stability: f64, retention: f64) -> f64 {
+        self.interval_rounding
+            .apply(self.inverse_forgetting_curve(stability, retention))
+            .clamp(1.0, self.maximum_interval_days() as f64)
+    }
+
+    /// Number of consecutive reviews with the given grade for a new card to
+    /// reach an interval of at least the target
+    ///
+    /// Each review after the first happens when the card is due. Targets
+    /// beyond the maximum interval are treated as the maximum interval. If
+    /// stability stops growing before the target is reached, as it does for
+    /// Again, the result is [u32::MAX].
+    pub fn reviews_to_interval(&self, target_days: i64, grade: Grade) -> u32 {
+        let target = target_days.min(self.maximum_interval_days()) as f64;
+        let mut difficulty = self.init_difficulty(grade);
+        let mut stability = self.init_stability(grade);
+        let mut interval = self.graded_interval(stability, grade);
+        let mut reviews = 1;
+        while interval < target {
+            let retrievability = self.forgetting_curve(interval, stability);
+            let next = self.next_stability(difficulty, stability, retrievability, grade);
+            if next.is_nan() || next <= stability {
+                return u32::MAX;
+            }
+            stability = next;
+            difficulty = self.next_difficulty(difficulty, grade);
+            interval = self.graded_interval(stability, grade);
+            reviews += 1;
+        }
+        reviews
+    }
+
+    /// Fractional days for the forgetting curve to reach the given
+    /// retrievability
+    pub(crate) fn inverse_forgetting_curve(&self, stability: f64, retrievability: f64) -> f64 {
+        match self.curve {
+            CurveKind::Power => {
+                stability / self.factor() * (retrievability.powf(1.0 / self.decay()) - 1.0)
+            }
+            CurveKind::Exponential => stability * retrievability.ln() / 0.9f64.ln(),
+        }
     }
 
     pub(crate) fn next_difficulty(&self, difficulty: f64, grade: Grade) -> f64 {
         let grade_int = grade as i32;
-        let next_difficulty = self.w[6].mul_add(-(grade_int as f64 - 3.0), difficulty);
+        let next_difficulty =
+            self.w[DIFFICULTY_DELTA].mul_add(-(grade_int as f64 - 3.0), difficulty);
         let mean_reversion =
             self.mean_reversion(self.init_difficulty(Grade::Easy), next_difficulty);
         mean_reversion.clamp(1.0, 10.0)
@@ -63,41 +406,113 @@ impl Parameters {
         }
     }
 
+    /// Stability of the card after a review with the given grade after the
+    /// given number of days
+    ///
+    /// This is the stability [Fsrs::next_card](crate::Fsrs::next_card)
+    /// would produce, without needing a review time. Reviews less than a
+    /// day later use [Parameters::short_term_stability].
+    pub fn projected_stability(&self, card: &Card, elapsed_days: f64, grade: Grade) -> f64 {
+        let stability = if elapsed_days < 1.0 {
+            self.short_term_stability(card.stability, grade)
+        } else {
+            let retrievability = self.forgetting_curve(elapsed_days, card.stability);
+            self.next_stability(card.difficulty, card.stability, retrievability, grade)
+        };
+        stability.max(self.minimum_stability)
+    }
+
+    /// Whole days until the next review if the card were reviewed with the
+    /// given grade after the given number of days
+    ///
+    /// This is the interval for [Parameters::projected_stability], using
+    /// the card's retention if it has one, before fuzz and the minimum
+    /// intervals for new cards.
+    pub fn next_interval_after_delay(&self, card: &Card, elapsed_days: f64, grade: Grade) -> i64 {
+        let stability = self.projected_stability(card, elapsed_days, grade);
+        let parameters = Self {
+            retention: card.retention.unwrap_or(self.retention),
+            ..*self
+        };
+        parameters.graded_interval(stability, grade) as i64
+    }
+
+    /// Stability after another review on the same day
+    ///
+    /// Retrievability is still about 1 within a day, so the long-term
+    /// stability update says little about such reviews. FSRS instead scales
+    /// the stability by the grade. In FSRS-6, higher stability damps the
+    /// change, and Good or Easy never lower the stability.
+    pub fn short_term_stability(&self, stability: f64, grade: Grade) -> f64 {
+        let grade = grade as i32 as f64;
+        let increase = (self.w[SHORT_TERM_STABILITY] * (grade - 3.0 + self.w[SHORT_TERM_GRADE]))
+            .exp()
+            * stability.powf(-self.w[SHORT_TERM_STABILITY_DECAY]);
+        let increase = match self.version() {
+            Version::Fsrs6 if grade >= 3.0 => increase.max(1.0),
+            _ => increase,
+        };
+        stability * increase
+    }
+
     pub fn next_recall_stability(
         &self,
         difficulty: f64,
         stability: f64,
         retrievability: f64,
         grade: Grade,
+    ) -> f64 {
+        stability * self.stability_increase(difficulty, stability, retrievability, grade)
+    }
+
+    /// Factor by which a successful review multiplies the stability, known
+    /// as SInc
+    ///
+    /// The increase is larger for easier cards, lower stability, and lower
+    /// retrievability at the time of the review.
+    pub fn stability_increase(
+        &self,
+        difficulty: f64,
+        stability: f64,
+        retrievability: f64,
+        grade: Grade,
     ) -> f64 {
         let modifier = match grade {
-            Grade::Hard => self.w[15],
-            Grade::Easy => self.w[16],
+            Grade::Hard => self.w[HARD_PENALTY],
+            Grade::Easy => self.w[EASY_BONUS],
             _ => 1.0,
         };
 
-        stability
-            * (((self.w[8]).exp()
-                * (11.0 - difficulty)
-                * stability.powf(-self.w[9])
-                * (((1.0 - retrievability) * self.w[10]).exp_m1()))
-            .mul_add(modifier, 1.0))
+        (self.w[RECALL_STABILITY].exp()
+            * (11.0 - difficulty)
+            * stability.powf(-self.w[RECALL_STABILITY_DECAY])
+            * (((1.0 - retrievability) * self.w[RECALL_RETRIEVABILITY]).exp_m1()))
+        .mul_add(modifier, 1.0)
     }
 
-    pub(crate) fn next_forget_stability(
+    /// Stability after forgetting a card, scaled by the lapse factor
+    ///
+    /// The new stability is lower for harder cards and higher for cards that
+    /// were stable or forgotten despite high retrievability.
+    pub fn next_forget_stability(
         &self,
         difficulty: f64,
         stability: f64,
         retrievability: f64,
     ) -> f64 {
-        self.w[11]
-            * difficulty.powf(-self.w[12])
-            * ((stability + 1.0).powf(self.w[13]) - 1.0)
-            * f64::exp((1.0 - retrievability) * self.w[14])
+        self.lapse_factor
+            * self.w[FORGET_STABILITY]
+            * difficulty.powf(-self.w[FORGET_DIFFICULTY])
+            * ((stability + 1.0).powf(self.w[FORGET_STABILITY_EXPONENT]) - 1.0)
+            * f64::exp((1.0 - retrievability) * self.w[FORGET_RETRIEVABILITY])
     }
 
-    fn mean_reversion(&self, initial: f64, current: f64) -> f64 {
-        self.w[7].mul_add(initial, (1.0 - self.w[7]) * current)
+    /// Pull a difficulty towards a target by the mean reversion weight
+    ///
+    /// After each review, difficulty moves this way towards the initial
+    /// difficulty for Easy, so that repeated Good reviews converge there.
+    pub fn mean_reversion(&self, initial: f64, current: f64) -> f64 {
+        self.w[MEAN_REVERSION].mul_add(initial, (1.0 - self.w[MEAN_REVERSION]) * current)
     }
 }
 
@@ -106,3 +521,701 @@ impl Default for Parameters {
         ParametersBuilder::new().build()
     }
 }
+
+/// Weights for any supported [Version]
+///
+/// Unused trailing weights are zero. The forgetting curve factor is kept
+/// alongside the weights so that scheduling avoids recomputing it.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub(crate) struct WeightSet {
+    w: WeightsV6,
+    version: Version,
+    factor: f64,
+}
+
+impl WeightSet {
+    fn new(w: WeightsV6, version: Version) -> Self {
+        let mut set = Self {
+            w,
+            version,
+            factor: Parameters::FACTOR,
+        };
+        set.update_factor();
+        set
+    }
+
+    pub(crate) fn as_slice(&self) -> &[f64] {
+        &self.w[..self.version.weight_count()]
+    }
+
+    /// Replace the weight at the given index
+    pub(crate) fn set(&mut self, index: usize, value: f64) {
+        self.w[index] = value;
+        self.update_factor();
+    }
+
+    fn update_factor(&mut self) {
+        self.factor = match self.version {
+            Version::Fsrs5 => Parameters::FACTOR,
+            Version::Fsrs6 => 0.9f64.powf(-1.0 / self.w[W_DECAY]) - 1.0,
+        };
+    }
+}
+
+impl From<Weights> for WeightSet {
+    fn from(weights: Weights) -> Self {
+        let mut w = [0.0; 21];
+        w[..19].copy_from_slice(&weights);
+        Self::new(w, Version::Fsrs5)
+    }
+}
+
+impl TryFrom<&[f64]> for WeightSet {
+    type Error = ParamError;
+
+    fn try_from(weights: &[f64]) -> Result<Self, Self::Error> {
+        match weights.len() {
+            17 => {
+                let mut w = [0.0; 19];
+                w[..17].copy_from_slice(weights);
+                w[4] = w[5].mul_add(2.0, w[4]);
+                w[5] = w[5].mul_add(3.0, 1.0).ln() / 3.0;
+                w[6] += 0.5;
+                Ok(w.into())
+            }
+            19 => Ok(Weights::try_from(weights).unwrap().into()),
+            21 => Ok(WeightsV6::try_from(weights).unwrap().into()),
+            count => Err(ParamError::WeightCount(count)),
+        }
+    }
+}
+
+impl From<WeightsV6> for WeightSet {
+    fn from(w: WeightsV6) -> Self {
+        Self::new(w, Version::Fsrs6)
+    }
+}
+
+impl Index<usize> for WeightSet {
+    type Output = f64;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.w[index]
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for WeightSet {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for WeightSet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let weights = Vec::<f64>::deserialize(deserializer)?;
+        weights
+            .as_slice()
+            .try_into()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Invalid FSRS parameters
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum ParamError {
+    /// The number of weights is not 17, 19, or 21
+    WeightCount(usize),
+    /// The weight at the given index is NaN or infinite
+    NonFiniteWeight(usize),
+    /// The weight at the given index is not a number
+    InvalidWeight(usize),
+    /// The stability weight at the given index is negative
+    NegativeWeight(usize),
+    /// The initial difficulty weight is outside the difficulty range [1, 10]
+    InitialDifficulty(f64),
+    /// The mean reversion weight is outside [0, 1]
+    MeanReversion(f64),
+    /// The FSRS-6 forgetting curve decay is not positive
+    Decay(f64),
+    /// The desired retention is outside (0, 1)
+    Retention(f64),
+    /// The maximum interval is less than one day
+    MaximumInterval(i64),
+    /// The day cutoff hour is not from 0 to 23
+    DayCutoffHour(u32),
+    /// The easy bonus is less than one or infinite
+    EasyBonus(f64),
+    /// The hard factor is outside (0, 1]
+    HardFactor(f64),
+    /// The graduating interval is less than one day
+    GraduatingInterval(i64),
+    /// The easy interval is less than one day
+    EasyInterval(i64),
+    /// The minimum stability is not positive and finite
+    MinimumStability(f64),
+    /// The lapse factor is not positive and finite
+    LapseFactor(f64),
+    /// The due time of day, as an hour and minute, is not a valid time
+    TimeOfDay(u32, u32),
+}
+
+impl Display for ParamError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamError::WeightCount(count) => {
+                write!(f, "expected 17, 19, or 21 weights, found {count}")
+            }
+            ParamError::NonFiniteWeight(index) => write!(f, "weight {index} is not finite"),
+            ParamError::InvalidWeight(index) => write!(f, "weight {index} is not a number"),
+            ParamError::NegativeWeight(index) => write!(f, "weight {index} is negative"),
+            ParamError::InitialDifficulty(d) => {
+                write!(f, "initial difficulty {d} is outside [1, 10]")
+            }
+            ParamError::MeanReversion(w) => write!(f, "mean reversion {w} is outside [0, 1]"),
+            ParamError::Decay(decay) => write!(f, "decay {decay} is not positive"),
+            ParamError::Retention(r) => write!(f, "retention {r} is outside (0, 1)"),
+            ParamError::MaximumInterval(days) => {
+                write!(f, "maximum interval of {days} days is less than one day")
+            }
+            ParamError::DayCutoffHour(hour) => write!(f, "day cutoff hour {hour} is not 0 to 23"),
+            ParamError::EasyBonus(bonus) => write!(f, "easy bonus {bonus} is less than 1"),
+            ParamError::HardFactor(factor) => write!(f, "hard factor {factor} is outside (0, 1]"),
+            ParamError::GraduatingInterval(days) => {
+                write!(f, "graduating interval of {days} days is less than one day")
+            }
+            ParamError::EasyInterval(days) => {
+                write!(f, "easy interval of {days} days is less than one day")
+            }
+            ParamError::MinimumStability(stability) => {
+                write!(f, "minimum stability {stability} is not positive")
+            }
+            ParamError::LapseFactor(factor) => write!(f, "lapse factor {factor} is not positive"),
+            ParamError::TimeOfDay(hour, minute) => {
+                write!(f, "time of day {hour:02}:{minute:02} is not valid")
+            }
+        }
+    }
+}
+
+impl Error for ParamError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{CurveKind, IntervalRounding, ParamError, Version, WeightsV6};
+    use crate::{
+        fsrs::tests::string_to_utc, weights, Card, Duration, Fsrs, Grade, Parameters,
+        ParametersBuilder,
+    };
+
+    #[test]
+    fn lower_retention_lengthens_intervals() {
+        let high = ParametersBuilder::new().retention(0.9).build();
+        let low = ParametersBuilder::new().retention(0.8).build();
+        for stability in [1.0, 5.0, 20.0, 100.0] {
+            assert!(low.next_interval(stability) > high.next_interval(stability));
+        }
+    }
+
+    #[test]
+    fn retrievability_at_due() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        for retention in [0.8, 0.85, 0.9, 0.95] {
+            let parameters = ParametersBuilder::new().retention(retention).build();
+            let fsrs = Fsrs::new(parameters);
+            let mut card = fsrs.next_card(None, now, Grade::Good);
+            while card.interval.num_days() < parameters.maximum_interval {
+                // The interval is rounded to the nearest day
+                let days = card.interval.num_days() as f64;
+                let earliest = parameters.forgetting_curve(days - 0.5, card.stability);
+                let latest = parameters.forgetting_curve(days + 0.5, card.stability);
+                let actual = parameters.retrievability_at_due(&card);
+                assert!((latest..=earliest).contains(&retention));
+                assert!((latest..=earliest).contains(&actual));
+                card = fsrs.next_card(Some(card), card.due(), Grade::Good);
+            }
+        }
+
+        let parameters = Parameters::default();
+        for stability in [5.0, 20.0, 100.0, 1000.0] {
+            let card = Card {
+                interval: Duration::days(parameters.next_interval(stability) as i64),
+                stability,
+                ..Fsrs::default().next_card(None, now, Grade::Good)
+            };
+            let actual = parameters.retrievability_at_due(&card);
+            assert!((actual - 0.9).abs() < 0.02, "{stability}: {actual}");
+        }
+    }
+
+    #[test]
+    fn difficulty_bounds() {
+        let mut w = Parameters::DEFAULT_WEIGHTS;
+        w[weights::DIFFICULTY_DELTA] = 5.0;
+        w[weights::MEAN_REVERSION] = 0.0;
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        for parameters in [
+            Parameters::default(),
+            ParametersBuilder::new().weights(w).build(),
+        ] {
+            let fsrs = Fsrs::new(parameters);
+            for grade in Grade::all() {
+                let mut card = fsrs.next_card(None, now, grade);
+                for _ in 0..100 {
+                    assert!((1.0..=10.0).contains(&card.difficulty));
+                    card = fsrs.next_card(Some(card), card.due(), grade);
+                }
+                if grade == Grade::Again {
+                    assert_eq!(card.difficulty, 10.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn next_interval_after_delay() {
+        let parameters = Parameters::default();
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let fsrs = Fsrs::new(parameters);
+        let card = fsrs.next_card(None, now, Grade::Good);
+        let on_time = card.interval.num_days() as f64;
+
+        let expected = fsrs.next_card(Some(card), card.due(), Grade::Good);
+        assert_eq!(
+            parameters.next_interval_after_delay(&card, on_time, Grade::Good),
+            expected.interval.num_days()
+        );
+        assert!(
+            parameters.next_interval_after_delay(&card, on_time + 10.0, Grade::Good)
+                > expected.interval.num_days()
+        );
+    }
+
+    #[test]
+    fn interval_rounding() {
+        for (rounding, days) in [
+            (IntervalRounding::Floor, 4.0),
+            (IntervalRounding::Round, 5.0),
+            (IntervalRounding::Ceil, 5.0),
+        ] {
+            assert_eq!(rounding.apply(4.6), days);
+            let parameters = ParametersBuilder::new().interval_rounding(rounding).build();
+            assert_eq!(parameters.next_interval(4.6), days);
+        }
+        assert_eq!(IntervalRounding::Ceil.apply(4.0), 4.0);
+        assert_eq!(
+            Parameters::default().interval_rounding,
+            IntervalRounding::Round
+        );
+    }
+
+    #[test]
+    fn stability_increase() {
+        let parameters = Parameters::default();
+        for grade in [Grade::Hard, Grade::Good, Grade::Easy] {
+            for (difficulty, stability, retrievability) in
+                [(5.0, 3.0, 0.9), (8.0, 40.0, 0.7), (2.0, 200.0, 0.95)]
+            {
+                let increase =
+                    parameters.stability_increase(difficulty, stability, retrievability, grade);
+                let next =
+                    parameters.next_recall_stability(difficulty, stability, retrievability, grade);
+                assert!(increase > 1.0);
+                assert!((stability * increase - next).abs() < 1e-9 * next);
+            }
+        }
+    }
+
+    #[test]
+    fn lapse_factor() {
+        let plain = Parameters::default();
+        let explicit = ParametersBuilder::new().lapse_factor(1.0).build();
+        let halved = ParametersBuilder::new().lapse_factor(0.5).build();
+        for (difficulty, stability, retrievability) in [(5.0, 3.0, 0.9), (8.0, 40.0, 0.7)] {
+            let forget = plain.next_forget_stability(difficulty, stability, retrievability);
+            assert_eq!(
+                explicit.next_forget_stability(difficulty, stability, retrievability),
+                forget
+            );
+            assert_eq!(
+                halved.next_forget_stability(difficulty, stability, retrievability),
+                forget * 0.5
+            );
+        }
+        assert_eq!(
+            ParametersBuilder::new().lapse_factor(0.0).try_build(),
+            Err(vec![ParamError::LapseFactor(0.0)])
+        );
+    }
+
+    #[test]
+    fn clone_with() {
+        let base = ParametersBuilder::new().maximum_interval(365).build();
+        for retention in [0.8, 0.85, 0.9, 0.95] {
+            let variant = base.clone_with_retention(retention).unwrap();
+            assert_eq!(variant.retention, retention);
+            assert_eq!(variant.maximum_interval, 365);
+        }
+        assert_eq!(
+            base.clone_with_retention(1.5),
+            Err(vec![ParamError::Retention(1.5)])
+        );
+        assert_eq!(
+            base.clone_with_maximum_interval(0),
+            Err(vec![ParamError::MaximumInterval(0)])
+        );
+        assert_eq!(base.with(|builder| builder), Ok(base));
+        assert_eq!(ParametersBuilder::from(base).build(), base);
+    }
+
+    #[test]
+    fn reviews_to_interval() {
+        let parameters = Parameters::default();
+        let hard = parameters.reviews_to_interval(365, Grade::Hard);
+        let good = parameters.reviews_to_interval(365, Grade::Good);
+        let easy = parameters.reviews_to_interval(365, Grade::Easy);
+        assert!(easy < good);
+        assert!(good < hard);
+        assert!(hard < u32::MAX);
+        assert_eq!(parameters.reviews_to_interval(365, Grade::Again), u32::MAX);
+        assert_eq!(parameters.reviews_to_interval(0, Grade::Good), 1);
+        assert_eq!(
+            parameters.reviews_to_interval(i64::MAX, Grade::Good),
+            parameters.reviews_to_interval(parameters.maximum_interval, Grade::Good)
+        );
+    }
+
+    #[test]
+    fn forgetting_curve() {
+        let parameters = Parameters::default();
+        assert_eq!(parameters.forgetting_curve(0.0, 3.0), 1.0);
+        assert_eq!(parameters.forgetting_curve(-2.0, 3.0), 1.0);
+
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = Fsrs::new(parameters).next_card(None, now, Grade::Good);
+        for days in [1, 3, 10, 100] {
+            let r = parameters.forgetting_curve(days as f64, card.stability);
+            assert_eq!(
+                r,
+                card.retrievability(&parameters, now + Duration::days(days))
+            );
+            assert!((0.0..=1.0).contains(&r));
+        }
+    }
+
+    #[test]
+    fn curve_kind() {
+        let power = Parameters::default();
+        let explicit = ParametersBuilder::new().curve(CurveKind::Power).build();
+        let exponential = ParametersBuilder::new()
+            .curve(CurveKind::Exponential)
+            .build();
+        assert_eq!(explicit, power);
+
+        for stability in [2.0, 10.0, 50.0] {
+            let r = exponential.forgetting_curve(stability, stability);
+            assert!((r - 0.9).abs() < 1e-12);
+            for days in [1.0, 30.0, 365.0] {
+                let r = exponential.forgetting_curve(days, stability);
+                assert!((r - 0.9f64.powf(days / stability)).abs() < 1e-12);
+            }
+            // The exponential curve decays faster in the long run
+            assert!(
+                exponential.forgetting_curve(10.0 * stability, stability)
+                    < power.forgetting_curve(10.0 * stability, stability)
+            );
+        }
+
+        // Each inverts its own curve
+        for parameters in [power, exponential] {
+            let interval = parameters.next_interval_for_retention(100.0, 0.7);
+            let r = parameters.forgetting_curve(interval, 100.0);
+            assert!((r - 0.7).abs() < 0.002);
+        }
+        assert_ne!(
+            exponential.next_interval_for_retention(100.0, 0.7),
+            power.next_interval_for_retention(100.0, 0.7)
+        );
+        assert_eq!(exponential.next_interval(100.0), power.next_interval(100.0));
+    }
+
+    #[test]
+    fn mean_reversion() {
+        let parameters = Parameters::default();
+        let target = parameters.init_difficulty(Grade::Easy);
+        assert!((parameters.mean_reversion(target, target) - target).abs() < 1e-12);
+        assert!(parameters.mean_reversion(target, 9.0) < 9.0);
+
+        let mut difficulty = 9.0;
+        for _ in 0..2000 {
+            let next = parameters.next_difficulty(difficulty, Grade::Good);
+            assert!(next < difficulty && next > target);
+            difficulty = next;
+        }
+        assert!((difficulty - target).abs() < 0.01);
+
+        let mut w = Parameters::DEFAULT_WEIGHTS;
+        w[7] = 1.5;
+        assert_eq!(
+            ParametersBuilder::new().weights(w).build().validate(),
+            Err(vec![ParamError::MeanReversion(1.5)])
+        );
+    }
+
+    #[test]
+    fn projected_stability() {
+        let parameters = Parameters::default();
+        let fsrs = Fsrs::new(parameters);
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = fsrs.next_card(None, now, Grade::Good);
+        for days in [0, 1, 5, 30] {
+            for grade in Grade::all() {
+                let next = fsrs.next_card(Some(card), now + Duration::days(days), grade);
+                assert_eq!(
+                    parameters.projected_stability(&card, days as f64, grade),
+                    next.stability
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn next_interval_for_retention() {
+        let parameters = ParametersBuilder::new().retention(0.85).build();
+        for stability in [0.5, 3.0, 42.0, 1000.0] {
+            assert_eq!(
+                parameters.next_interval_for_retention(stability, 0.85),
+                parameters.next_interval(stability)
+            );
+        }
+        assert!(
+            parameters.next_interval_for_retention(30.0, 0.8)
+                > parameters.next_interval_for_retention(30.0, 0.9)
+        );
+    }
+
+    const FSRS_6_WEIGHTS: WeightsV6 = [
+        0.212, 1.2931, 2.3065, 8.2956, 6.4133, 0.8334, 3.0194, 0.001, 1.8722, 0.1666, 0.796,
+        1.4835, 0.0614, 0.2629, 1.6483, 0.6014, 1.8729, 0.5425, 0.0912, 0.0658, 0.1542,
+    ];
+
+    #[test]
+    fn version() {
+        assert_eq!(Parameters::default().version(), Version::Fsrs5);
+        assert_eq!(Parameters::default().weights().len(), 19);
+
+        let parameters = ParametersBuilder::new().weights_v6(FSRS_6_WEIGHTS).build();
+        assert_eq!(parameters.version(), Version::Fsrs6);
+        assert_eq!(parameters.weights(), FSRS_6_WEIGHTS.as_slice());
+    }
+
+    #[test]
+    fn fsrs_6_decay() {
+        let parameters = ParametersBuilder::new()
+            .weights_v6(FSRS_6_WEIGHTS)
+            .retention(0.8)
+            .build();
+        let fsrs = Fsrs::new(parameters);
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let intervals = fsrs
+            .preview(None, now)
+            .map(|(_, card)| card.interval.num_days());
+        assert_eq!(intervals.into_array(), [1, 4, 8, 28]);
+
+        // Retrievability is 90% when the elapsed days equal the stability
+        // regardless of the decay
+        let r = parameters.forgetting_curve(FSRS_6_WEIGHTS[3], FSRS_6_WEIGHTS[3]);
+        assert!((r - 0.9).abs() < 1e-12);
+
+        let fsrs_5 = ParametersBuilder::new().retention(0.8).build();
+        assert_eq!(fsrs_5.next_interval(FSRS_6_WEIGHTS[3]), 20.0);
+    }
+
+    #[test]
+    fn cached_factor() {
+        let fresh = |parameters: &Parameters| 0.9f64.powf(1.0 / parameters.decay()) - 1.0;
+        let mut fsrs_6 = ParametersBuilder::new().weights_v6(FSRS_6_WEIGHTS).build();
+        for parameters in [
+            Parameters::default(),
+            ParametersBuilder::new().retention(0.7).build(),
+            fsrs_6,
+            Parameters::from_weights(&FSRS_6_WEIGHTS[..17]).unwrap(),
+        ] {
+            assert!((parameters.factor() - fresh(&parameters)).abs() < 1e-12);
+        }
+
+        for decay in [0.1, 0.3, 0.8] {
+            fsrs_6.w.set(20, decay);
+            assert!((fsrs_6.factor() - fresh(&fsrs_6)).abs() < 1e-12);
+            let r = fsrs_6.forgetting_curve(10.0, 10.0);
+            assert!((r - 0.9).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn from_weights() {
+        let fsrs_6 = Parameters::from_weights(&FSRS_6_WEIGHTS).unwrap();
+        assert_eq!(fsrs_6.version(), Version::Fsrs6);
+        assert_eq!(fsrs_6.weights(), FSRS_6_WEIGHTS.as_slice());
+
+        let fsrs_5 = Parameters::from_weights(&Parameters::DEFAULT_WEIGHTS).unwrap();
+        assert_eq!(fsrs_5, Parameters::default());
+
+        let fsrs_4_5 = [
+            0.4872, 1.4003, 3.7145, 13.8206, 5.1618, 1.2298, 0.8975, 0.031, 1.6474, 0.1367, 1.0461,
+            2.1072, 0.0793, 0.3246, 1.587, 0.2272, 2.8755,
+        ];
+        let upgraded = Parameters::from_weights(&fsrs_4_5).unwrap();
+        assert_eq!(upgraded.version(), Version::Fsrs5);
+        assert_eq!(upgraded.weights()[..4], fsrs_4_5[..4]);
+        assert_eq!(upgraded.weights()[17..], [0.0, 0.0]);
+        // FSRS-4.5 initial difficulty was w[4] - (grade - 3) * w[5], which the
+        // conversion matches exactly for Again and Easy
+        let again = fsrs_4_5[4] + 2.0 * fsrs_4_5[5];
+        let easy = fsrs_4_5[4] - fsrs_4_5[5];
+        assert!((upgraded.init_difficulty(Grade::Again) - again).abs() < 1e-9);
+        assert!((upgraded.init_difficulty(Grade::Easy) - easy).abs() < 1e-9);
+
+        assert_eq!(
+            Parameters::from_weights(&[1.0; 18]),
+            Err(ParamError::WeightCount(18))
+        );
+        assert_eq!(
+            ParamError::WeightCount(18).to_string(),
+            "expected 17, 19, or 21 weights, found 18"
+        );
+    }
+
+    #[test]
+    fn anki_string() {
+        let anki = "[0.40255, 1.18385, 3.173, 15.69105, 7.1949, 0.5345, 1.4604, 0.0046, \
+                    1.54575, 0.1192, 1.01925, 1.9395, 0.11, 0.29605, 2.2698, 0.2315, 2.9898, \
+                    0.51655, 0.6621]";
+        let parameters = Parameters::from_anki_string(anki).unwrap();
+        assert_eq!(parameters.weights(), Parameters::DEFAULT_WEIGHTS.as_slice());
+        assert_eq!(parameters.to_anki_string(), anki);
+        assert_eq!(
+            Parameters::from_anki_string(&parameters.to_anki_string()),
+            Ok(parameters)
+        );
+
+        let bare = Parameters::from_anki_string(
+            " 0.40255,1.18385 ,3.173,15.69105,7.1949,0.5345,\
+            1.4604,0.0046,1.54575,0.1192,1.01925,1.9395,0.11,0.29605,2.2698,0.2315,2.9898,\
+            0.51655,0.6621\n",
+        );
+        assert_eq!(bare, Ok(parameters));
+
+        let nan = anki.replace("3.173", "NaN");
+        assert_eq!(
+            Parameters::from_anki_string(&nan),
+            Err(ParamError::NonFiniteWeight(2))
+        );
+        let inf = anki.replace("0.6621", "inf");
+        assert_eq!(
+            Parameters::from_anki_string(&inf),
+            Err(ParamError::NonFiniteWeight(18))
+        );
+        assert_eq!(
+            Parameters::from_anki_string("[0.4, x]"),
+            Err(ParamError::InvalidWeight(1))
+        );
+        assert_eq!(
+            Parameters::from_anki_string("[]"),
+            Err(ParamError::InvalidWeight(0))
+        );
+    }
+
+    #[test]
+    fn validate() {
+        assert_eq!(Parameters::default().validate(), Ok(()));
+        assert_eq!(
+            ParametersBuilder::new()
+                .weights_v6(FSRS_6_WEIGHTS)
+                .build()
+                .validate(),
+            Ok(())
+        );
+
+        let mut w = Parameters::DEFAULT_WEIGHTS;
+        w[1] = -1.0;
+        w[4] = 12.0;
+        w[9] = f64::NAN;
+        let invalid = ParametersBuilder::new().weights(w).build();
+        assert_eq!(
+            invalid.validate(),
+            Err(vec![
+                ParamError::NegativeWeight(1),
+                ParamError::NonFiniteWeight(9),
+                ParamError::InitialDifficulty(12.0),
+            ])
+        );
+
+        let mut w = FSRS_6_WEIGHTS;
+        w[20] = 0.0;
+        let invalid = ParametersBuilder::new()
+            .weights_v6(w)
+            .retention(1.0)
+            .maximum_interval(0)
+            .build();
+        assert_eq!(
+            invalid.validate(),
+            Err(vec![
+                ParamError::Decay(0.0),
+                ParamError::Retention(1.0),
+                ParamError::MaximumInterval(0),
+            ])
+        );
+
+        assert_eq!(
+            ParametersBuilder::new().retention(0.0).try_build(),
+            Err(vec![ParamError::Retention(0.0)])
+        );
+        assert_eq!(
+            ParametersBuilder::new()
+                .day_cutoff(24, chrono::FixedOffset::east_opt(0).unwrap())
+                .try_build(),
+            Err(vec![ParamError::DayCutoffHour(24)])
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let parameters = ParametersBuilder::new()
+            .retention(0.85)
+            .maximum_interval(365)
+            .build();
+        let json = serde_json::to_value(parameters).unwrap();
+        assert_eq!(json["retention"], 0.85);
+        assert_eq!(json["maximum_interval"], 365);
+        assert_eq!(json["w"].as_array().unwrap().len(), 19);
+
+        let parsed: Parameters = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, parameters);
+
+        // Parsed parameters are not validated, but still schedule cards
+        let parsed: Parameters = serde_json::from_str(r#"{ "maximum_interval": 0 }"#).unwrap();
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = Fsrs::new(parsed).next_card(None, now, Grade::Good);
+        assert_eq!(card.interval.num_days(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_fsrs_6() {
+        let parameters = ParametersBuilder::new().weights_v6(FSRS_6_WEIGHTS).build();
+        let json = serde_json::to_string(&parameters).unwrap();
+        let parsed: Parameters = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.version(), Version::Fsrs6);
+        assert_eq!(parsed, parameters);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_short_weights() {
+        let json = r#"{ "w": [0.4, 1.2, 3.1], "retention": 0.9, "maximum_interval": 36500 }"#;
+        assert!(serde_json::from_str::<Parameters>(json).is_err());
+    }
+}
@@ -1,11 +1,56 @@
-use crate::{parameters::Weights, Parameters};
+#[cfg(feature = "chrono-tz")]
+use crate::day_cutoff::DueTime;
+use crate::{
+    day_cutoff::DayCutoff,
+    parameters::{WeightSet, Weights, WeightsV6},
+    CurveKind, IntervalRounding, ParamError, Parameters,
+};
+use chrono::FixedOffset;
 
 /// Builder for [`Parameters`]
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Default)]
 pub struct ParametersBuilder {
     retention: Option<f64>,
-    maximum_interval: Option<i32>,
-    w: Option<Weights>,
+    maximum_interval: Option<i64>,
+    w: Option<WeightSet>,
+    enable_fuzz: Option<bool>,
+    fuzz_seed: Option<u64>,
+    day_cutoff: Option<DayCutoff>,
+    leech_threshold: Option<u32>,
+    easy_bonus: Option<f64>,
+    hard_factor: Option<f64>,
+    graduating_interval: Option<i64>,
+    easy_interval: Option<i64>,
+    curve: Option<CurveKind>,
+    minimum_stability: Option<f64>,
+    interval_rounding: Option<IntervalRounding>,
+    lapse_factor: Option<f64>,
+    #[cfg(feature = "chrono-tz")]
+    due_time: Option<DueTime>,
+}
+
+impl From<Parameters> for ParametersBuilder {
+    fn from(parameters: Parameters) -> Self {
+        Self {
+            retention: Some(parameters.retention),
+            maximum_interval: Some(parameters.maximum_interval),
+            w: Some(parameters.w),
+            enable_fuzz: Some(parameters.enable_fuzz),
+            fuzz_seed: Some(parameters.fuzz_seed),
+            day_cutoff: parameters.day_cutoff,
+            leech_threshold: Some(parameters.leech_threshold),
+            easy_bonus: Some(parameters.easy_bonus),
+            hard_factor: Some(parameters.hard_factor),
+            graduating_interval: Some(parameters.graduating_interval),
+            easy_interval: Some(parameters.easy_interval),
+            curve: Some(parameters.curve),
+            minimum_stability: Some(parameters.minimum_stability),
+            interval_rounding: Some(parameters.interval_rounding),
+            lapse_factor: Some(parameters.lapse_factor),
+            #[cfg(feature = "chrono-tz")]
+            due_time: parameters.due_time,
+        }
+    }
 }
 
 impl ParametersBuilder {
@@ -19,13 +64,36 @@ impl ParametersBuilder {
         Parameters {
             retention: self.retention.unwrap_or(0.9),
             maximum_interval: self.maximum_interval.unwrap_or(36500),
-            w: self.w.unwrap_or(Parameters::DEFAULT_WEIGHTS),
+            w: self.w.unwrap_or(Parameters::DEFAULT_WEIGHTS.into()),
+            enable_fuzz: self.enable_fuzz.unwrap_or(false),
+            fuzz_seed: self.fuzz_seed.unwrap_or(0),
+            day_cutoff: self.day_cutoff,
+            leech_threshold: self.leech_threshold.unwrap_or(8),
+            easy_bonus: self.easy_bonus.unwrap_or(1.0),
+            hard_factor: self.hard_factor.unwrap_or(1.0),
+            graduating_interval: self.graduating_interval.unwrap_or(1),
+            easy_interval: self.easy_interval.unwrap_or(1),
+            curve: self.curve.unwrap_or_default(),
+            minimum_stability: self.minimum_stability.unwrap_or(0.01),
+            interval_rounding: self.interval_rounding.unwrap_or_default(),
+            lapse_factor: self.lapse_factor.unwrap_or(1.0),
+            #[cfg(feature = "chrono-tz")]
+            due_time: self.due_time,
         }
     }
 
+    /// Build the [Parameters] like [ParametersBuilder::build], then check them
+    /// with [Parameters::validate]
+    pub fn try_build(self) -> Result<Parameters, Vec<ParamError>> {
+        let parameters = self.build();
+        parameters.validate()?;
+        Ok(parameters)
+    }
+
     /// Set the desired retention rate
     ///
-    /// Default is 0.9
+    /// This is the probability of recalling a card when it comes due, in the
+    /// range (0, 1). Lower retention produces longer intervals. Default is 0.9
     pub fn retention(mut self, request_retention: f64) -> Self {
         self.retention = Some(request_retention);
         self
@@ -34,14 +102,206 @@ impl ParametersBuilder {
     /// Set the maximum interval between reviews in days
     ///
     /// Default is 36,500
-    pub fn maximum_interval(mut self, maximum_interval: i32) -> Self {
+    pub fn maximum_interval(mut self, maximum_interval: i64) -> Self {
         self.maximum_interval = Some(maximum_interval);
         self
     }
 
-    /// Set the FSRS algorithm weights
+    /// Set whether to randomly adjust intervals of at least three days
+    ///
+    /// Fuzz spreads out the reviews of cards that were studied together.
+    /// Default is false
+    pub fn enable_fuzz(mut self, enable_fuzz: bool) -> Self {
+        self.enable_fuzz = Some(enable_fuzz);
+        self
+    }
+
+    /// Set the seed for interval fuzz
+    ///
+    /// The fuzz for a review is determined by the seed, the review time, and
+    /// the card's stability. Default is 0
+    pub fn fuzz_seed(mut self, fuzz_seed: u64) -> Self {
+        self.fuzz_seed = Some(fuzz_seed);
+        self
+    }
+
+    /// Set the local hour of the day when one study day ends and the next
+    /// begins
+    ///
+    /// Cards become due at the cutoff instead of at the exact time of day they
+    /// were reviewed. A review shortly after midnight but before the cutoff
+    /// belongs to the previous study day. Default is no cutoff
+    pub fn day_cutoff(mut self, hour: u32, utc_offset: FixedOffset) -> Self {
+        self.day_cutoff = Some(DayCutoff {
+            hour,
+            utc_offset: utc_offset.local_minus_utc(),
+        });
+        self
+    }
+
+    /// Set the number of lapses at which a card becomes a leech
+    ///
+    /// See [Card::is_leech](crate::Card::is_leech). Zero disables leech
+    /// detection. Default is 8
+    pub fn leech_threshold(mut self, leech_threshold: u32) -> Self {
+        self.leech_threshold = Some(leech_threshold);
+        self
+    }
+
+    /// Set the multiplier for intervals after an Easy review
+    ///
+    /// This mimics the easy bonus of SM-2 schedulers and must be at least 1.
+    /// Default is 1, which leaves FSRS intervals unchanged
+    pub fn easy_bonus(mut self, easy_bonus: f64) -> Self {
+        self.easy_bonus = Some(easy_bonus);
+        self
+    }
+
+    /// Set the multiplier for intervals after a Hard review
+    ///
+    /// This mimics the hard interval of SM-2 schedulers and must be in
+    /// (0, 1]. Default is 1, which leaves FSRS intervals unchanged
+    pub fn hard_factor(mut self, hard_factor: f64) -> Self {
+        self.hard_factor = Some(hard_factor);
+        self
+    }
+
+    /// Set the local time of day when cards become due
+    ///
+    /// Cards become due at this time on the day the interval ends, no matter
+    /// what time they were reviewed. Times skipped by daylight saving time
+    /// move forward to the next valid time. This takes the place of the
+    /// [day cutoff](ParametersBuilder::day_cutoff). Default is no time of
+    /// day, which keeps the time of the review
+    #[cfg(feature = "chrono-tz")]
+    pub fn due_at_time_of_day(mut self, hour: u32, minute: u32, tz: chrono_tz::Tz) -> Self {
+        self.due_time = Some(DueTime { hour, minute, tz });
+        self
+    }
+
+    /// Set the multiplier for the stability of a card after it is forgotten
+    ///
+    /// Values below 1 make cards recover more slowly from a lapse. Must be
+    /// positive. Default is 1, which leaves FSRS stability unchanged
+    pub fn lapse_factor(mut self, lapse_factor: f64) -> Self {
+        self.lapse_factor = Some(lapse_factor);
+        self
+    }
+
+    /// Set the shortest interval in days after first reviewing a card with
+    /// Hard or Good
+    ///
+    /// Default is 1, which leaves FSRS intervals unchanged
+    pub fn graduating_interval(mut self, graduating_interval: i64) -> Self {
+        self.graduating_interval = Some(graduating_interval);
+        self
+    }
+
+    /// Set the shortest interval in days after first reviewing a card with
+    /// Easy
+    ///
+    /// Default is 1, which leaves FSRS intervals unchanged
+    pub fn easy_interval(mut self, easy_interval: i64) -> Self {
+        self.easy_interval = Some(easy_interval);
+        self
+    }
+
+    /// Set the shape of the forgetting curve
+    ///
+    /// Default is [CurveKind::Power]
+    pub fn curve(mut self, curve: CurveKind) -> Self {
+        self.curve = Some(curve);
+        self
+    }
+
+    /// Set how computed intervals are rounded to whole days
+    ///
+    /// Default is [IntervalRounding::Round]
+    pub fn interval_rounding(mut self, interval_rounding: IntervalRounding) -> Self {
+        self.interval_rounding = Some(interval_rounding);
+        self
+    }
+
+    /// Set the smallest stability a review can leave a card with
+    ///
+    /// Repeatedly forgetting a card lowers its stability, and this keeps it
+    /// from approaching zero. Default is 0.01
+    pub fn minimum_stability(mut self, minimum_stability: f64) -> Self {
+        self.minimum_stability = Some(minimum_stability);
+        self
+    }
+
+    /// Set the FSRS-5 algorithm weights
     pub fn weights(mut self, weights: Weights) -> Self {
-        self.w = Some(weights);
+        self.w = Some(weights.into());
+        self
+    }
+
+    /// Set the FSRS-6 algorithm weights, where the last weight is the
+    /// forgetting curve decay
+    pub fn weights_v6(mut self, weights: WeightsV6) -> Self {
+        self.w = Some(weights.into());
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParamError, ParametersBuilder};
+
+    #[test]
+    fn chain() -> Result<(), Vec<ParamError>> {
+        let parameters = ParametersBuilder::new()
+            .retention(0.85)
+            .maximum_interval(365)
+            .enable_fuzz(true)
+            .fuzz_seed(42)
+            .try_build()?;
+        assert_eq!(parameters.retention, 0.85);
+        assert_eq!(parameters.maximum_interval, 365);
+        assert!(parameters.enable_fuzz);
+        assert_eq!(parameters.fuzz_seed, 42);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_retention() {
+        assert_eq!(
+            ParametersBuilder::new().retention(1.5).try_build(),
+            Err(vec![ParamError::Retention(1.5)])
+        );
+    }
+
+    #[test]
+    fn invalid_multipliers() {
+        assert_eq!(
+            ParametersBuilder::new()
+                .easy_bonus(0.9)
+                .hard_factor(1.2)
+                .try_build(),
+            Err(vec![
+                ParamError::EasyBonus(0.9),
+                ParamError::HardFactor(1.2)
+            ])
+        );
+        assert_eq!(
+            ParametersBuilder::new().hard_factor(0.0).try_build(),
+            Err(vec![ParamError::HardFactor(0.0)])
+        );
+        assert_eq!(
+            ParametersBuilder::new()
+                .graduating_interval(0)
+                .easy_interval(-1)
+                .try_build(),
+            Err(vec![
+                ParamError::GraduatingInterval(0),
+                ParamError::EasyInterval(-1)
+            ])
+        );
+        assert!(ParametersBuilder::new()
+            .easy_bonus(1.3)
+            .hard_factor(0.8)
+            .try_build()
+            .is_ok());
+    }
+}
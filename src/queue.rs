@@ -0,0 +1,109 @@
+use crate::{Card, Grade, Time};
+
+/// Cards to study in a session, as indices into the collection given to
+/// [build_queue]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Queue {
+    /// Due cards that were forgotten at their last review, most overdue first
+    pub relearning: Vec<usize>,
+    /// Other due cards, most overdue first
+    pub review: Vec<usize>,
+    /// Cards that have never been reviewed, in collection order
+    pub new: Vec<usize>,
+}
+
+impl Queue {
+    /// Number of cards in the queue
+    pub fn len(&self) -> usize {
+        self.relearning.len() + self.review.len() + self.new.len()
+    }
+
+    /// Whether the queue has no cards
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Choose the cards to study at the given time
+///
+/// At most `review_limit` review cards and `new_limit` new cards are
+/// chosen. Like in Anki, cards being relearned after a lapse do not count
+/// towards the review limit. Cards with the same due time keep their order
+/// in the collection, so the queue depends only on the inputs.
+pub fn build_queue(
+    cards: &[Option<Card>],
+    now: Time,
+    new_limit: usize,
+    review_limit: usize,
+) -> Queue {
+    let mut queue = Queue::default();
+    for (i, card) in cards.iter().enumerate() {
+        match card {
+            None if queue.new.len() < new_limit => queue.new.push(i),
+            None => {}
+            Some(card) if !card.is_due(now) => {}
+            Some(card) if card.grade == Grade::Again => queue.relearning.push(i),
+            Some(_) => queue.review.push(i),
+        }
+    }
+
+    let due = |&i: &usize| cards[i].map(|card| card.due());
+    queue.relearning.sort_by_key(due);
+    queue.review.sort_by_key(due);
+    queue.review.truncate(review_limit);
+    queue
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build_queue;
+    use crate::{fsrs::tests::string_to_utc, Card, Duration, Grade};
+
+    #[test]
+    fn partition() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = |days_overdue: i64, grade: Grade| {
+            Some(Card {
+                grade,
+                interval: Duration::days(1),
+                ..Card::from_memory_state(5.0, 5.0, now - Duration::days(days_overdue + 1))
+            })
+        };
+        let cards = [
+            None,
+            card(1, Grade::Good),
+            card(-2, Grade::Good),
+            card(3, Grade::Again),
+            card(5, Grade::Hard),
+            None,
+            card(0, Grade::Again),
+            card(3, Grade::Easy),
+        ];
+
+        let queue = build_queue(&cards, now, 10, 10);
+        assert_eq!(queue.relearning, [3, 6]);
+        assert_eq!(queue.review, [4, 7, 1]);
+        assert_eq!(queue.new, [0, 5]);
+        assert_eq!(queue.len(), 7);
+        assert_eq!(build_queue(&cards, now, 10, 10), queue);
+    }
+
+    #[test]
+    fn limits() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let mut cards = vec![None; 5];
+        cards.extend((0..5).map(|i| {
+            Some(Card {
+                interval: Duration::days(1),
+                ..Card::from_memory_state(5.0, 5.0, now - Duration::days(i + 1))
+            })
+        }));
+
+        let queue = build_queue(&cards, now, 2, 3);
+        assert_eq!(queue.new, [0, 1]);
+        assert_eq!(queue.review, [9, 8, 7]);
+
+        let queue = build_queue(&cards, now, 0, 0);
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{Card, Grade, Review, Time};
+
+/// Record of a review and its outcome, suitable for storing review history
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReviewLog {
+    /// Difficulty of the review
+    pub grade: Grade,
+    /// When the review took place
+    pub reviewed_at: Time,
+    /// Whole days since the previous review, or 0 for the first review
+    pub elapsed_days: i64,
+    /// Whole days until the next review
+    pub scheduled_days: i64,
+    /// When the card is next due
+    pub due: Time,
+    /// FSRS memory stability after the review
+    pub stability: f64,
+    /// FSRS memory difficulty after the review
+    pub difficulty: f64,
+}
+
+impl ReviewLog {
+    /// Create a log of the review that turned `previous` into `card`
+    ///
+    /// `previous` is None if it was the card's first review.
+    pub fn new(previous: Option<Card>, card: Card) -> Self {
+        Self {
+            grade: card.grade,
+            reviewed_at: card.reviewed_at,
+            elapsed_days: previous.map_or(0, |previous| previous.elapsed_days(card.reviewed_at)),
+            scheduled_days: card.interval.num_days(),
+            due: card.due(),
+            stability: card.stability,
+            difficulty: card.difficulty,
+        }
+    }
+}
+
+impl From<ReviewLog> for Review {
+    fn from(log: ReviewLog) -> Self {
+        Self::new(log.grade, log.reviewed_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fsrs::tests::string_to_utc, Duration, Fsrs, Grade, ReviewLog};
+
+    #[test]
+    fn elapsed_days() {
+        let fsrs = Fsrs::default();
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let first = fsrs.next_card(None, now, Grade::Good);
+        let later = now + Duration::days(5);
+        let second = fsrs.next_card(Some(first), later, Grade::Hard);
+
+        let log = ReviewLog::new(None, first);
+        assert_eq!(log.elapsed_days, 0);
+        assert_eq!(log.due, first.due());
+
+        let log = ReviewLog::new(Some(first), second);
+        assert_eq!(log.grade, Grade::Hard);
+        assert_eq!(log.reviewed_at, later);
+        assert_eq!(log.elapsed_days, 5);
+        assert_eq!(log.scheduled_days, second.interval.num_days());
+        assert_eq!(log.stability, second.stability);
+        assert_eq!(
+            fsrs.reviewed([log]),
+            Some(fsrs.next_card(None, later, Grade::Hard))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let log = ReviewLog::new(None, Fsrs::default().next_card(None, now, Grade::Easy));
+        let json = serde_json::to_string(&log).unwrap();
+        assert_eq!(serde_json::from_str::<ReviewLog>(&json).unwrap(), log);
+    }
+}
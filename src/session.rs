@@ -0,0 +1,99 @@
+use crate::{Card, Fsrs, Grade, Review, Time};
+
+/// A card being reviewed over time, created by [Fsrs::session]
+///
+/// The session keeps the latest state of the card, so each review only needs
+/// its grade and time.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Session<'a, C> {
+    fsrs: &'a Fsrs<C>,
+    card: Option<Card>,
+}
+
+impl<'a, C> Session<'a, C> {
+    pub(crate) fn new(fsrs: &'a Fsrs<C>, card: Option<Card>) -> Self {
+        Self { fsrs, card }
+    }
+
+    /// Review the card, returning its new state
+    pub fn next(&mut self, grade: Grade, now: Time) -> Card {
+        let card = self.fsrs.next_card(self.card, now, grade);
+        self.card = Some(card);
+        card
+    }
+
+    /// The current state of the card, or None if it hasn't been reviewed
+    pub fn card(&self) -> Option<Card> {
+        self.card
+    }
+
+    /// Review the card lazily for each of the given reviews, producing its
+    /// state after each one
+    pub fn replay<I>(mut self, reviews: I) -> impl Iterator<Item = Card> + 'a
+    where
+        I: IntoIterator,
+        I::Item: Into<Review>,
+        I::IntoIter: 'a,
+    {
+        reviews.into_iter().map(move |review| {
+            let review = review.into();
+            self.next(review.grade, review.when)
+        })
+    }
+
+    /// Like [Session::replay], but sorting the reviews by time first, for
+    /// logs that may be out of chronological order
+    ///
+    /// The reviews are collected up front, and those at the same time keep
+    /// their order.
+    pub fn replay_sorted<I>(self, reviews: I) -> impl Iterator<Item = Card> + 'a
+    where
+        I: IntoIterator,
+        I::Item: Into<Review>,
+    {
+        let mut reviews: Vec<Review> = reviews.into_iter().map(Into::into).collect();
+        reviews.sort_by_key(|review| review.when);
+        self.replay(reviews)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fsrs::tests::{string_to_utc, TEST_GRADES, WEIGHTS},
+        Fsrs, Parameters, Review,
+    };
+
+    #[test]
+    fn intervals() {
+        let fsrs = Fsrs::new(Parameters {
+            w: WEIGHTS.into(),
+            ..Default::default()
+        });
+        let mut session = fsrs.session(None);
+        assert_eq!(session.card(), None);
+        let mut now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let mut reviews = vec![];
+        let mut intervals = vec![];
+        for grade in TEST_GRADES {
+            let card = session.next(grade, now);
+            assert_eq!(session.card(), Some(card));
+            reviews.push(Review::new(grade, now));
+            intervals.push(card.interval.num_days());
+            now += card.interval;
+        }
+        assert_eq!(
+            intervals,
+            [3, 13, 48, 155, 445, 1158, 17, 3, 9, 27, 74, 190, 457]
+        );
+
+        let replayed: Vec<_> = fsrs.session(None).replay(reviews.iter().copied()).collect();
+        assert_eq!(replayed.last().copied(), session.card());
+        assert_eq!(replayed.last().copied(), fsrs.reviewed(reviews.clone()));
+
+        let mut shuffled = reviews.clone();
+        shuffled.reverse();
+        let sorted: Vec<_> = fsrs.session(None).replay_sorted(shuffled).collect();
+        assert_eq!(sorted, replayed);
+    }
+}
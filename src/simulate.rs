@@ -0,0 +1,285 @@
+use crate::{rng::Rng, Card, Duration, Fsrs, Grade, Parameters, Time};
+
+/// Outcome of [simulate]
+#[derive(Debug, Clone, PartialEq, PartialOrd, Default)]
+pub struct SimulationResult {
+    /// Number of reviews on each simulated day
+    pub daily_reviews: Vec<usize>,
+    /// State of each card at the end of the simulation
+    pub cards: Vec<Card>,
+}
+
+impl SimulationResult {
+    /// Number of reviews over the whole simulation
+    pub fn total_reviews(&self) -> usize {
+        self.daily_reviews.iter().sum()
+    }
+
+    /// The stability and difficulty of each card at the end of the simulation
+    pub fn memory_states(&self) -> Vec<(f64, f64)> {
+        self.cards.iter().map(Card::memory_state).collect()
+    }
+}
+
+/// Project the reviews of a deck over the given number of days
+///
+/// Starting at `start`, each day reviews the cards that are due, asking the
+/// policy for the grade of each review. The policy can model recall however
+/// it likes, for example by sampling [Card::retrievability].
+pub fn simulate(
+    parameters: Parameters,
+    deck: Vec<Card>,
+    start: Time,
+    days: usize,
+    mut policy: impl FnMut(&Card, Time) -> Grade,
+) -> SimulationResult {
+    let fsrs = Fsrs::new(parameters);
+    let mut cards = deck;
+    let mut daily_reviews = Vec::with_capacity(days);
+    for day in 0..days {
+        let now = start + Duration::days(day as i64);
+        let mut reviews = 0;
+        for card in cards.iter_mut() {
+            if card.is_due(now) {
+                let grade = policy(card, now);
+                *card = fsrs.next_card(Some(*card), now, grade);
+                reviews += 1;
+            }
+        }
+        daily_reviews.push(reviews);
+    }
+    SimulationResult {
+        daily_reviews,
+        cards,
+    }
+}
+
+/// Outcome of [simulate_student]
+#[derive(Debug, Clone, PartialEq, PartialOrd, Default)]
+pub struct StudentResult {
+    /// Portion of reviews that were recalled, or NaN if there were none
+    pub achieved_retention: f64,
+    /// Number of reviews on each simulated day
+    pub daily_reviews: Vec<usize>,
+}
+
+impl StudentResult {
+    /// Number of reviews over the whole simulation
+    pub fn total_reviews(&self) -> usize {
+        self.daily_reviews.iter().sum()
+    }
+}
+
+/// Measure the retention achieved by scheduling with one set of parameters
+/// for a student whose memory follows another
+///
+/// Cards are scheduled with `model` and each review is recalled with the
+/// probability `truth` gives for the student's actual memory of the card,
+/// which is tracked separately. Recalled cards are graded Good and others
+/// Again. The deck's memory states are used as the starting point for both.
+/// Recall is sampled from the seed, so the result is reproducible.
+pub fn simulate_student(
+    truth: Parameters,
+    model: Parameters,
+    deck: Vec<Card>,
+    start: Time,
+    days: usize,
+    seed: u64,
+) -> StudentResult {
+    let model_fsrs = Fsrs::new(model);
+    let true_fsrs = Fsrs::new(truth);
+    let mut rng = Rng::new(seed);
+    let mut cards: Vec<_> = deck.into_iter().map(|card| (card, card)).collect();
+    let mut daily_reviews = Vec::with_capacity(days);
+    let mut recalled = 0;
+    for day in 0..days {
+        let now = start + Duration::days(day as i64);
+        let mut reviews = 0;
+        for (scheduled, actual) in cards.iter_mut() {
+            if scheduled.is_due(now) {
+                let grade = if rng.next_f64() < actual.retrievability(&truth, now) {
+                    recalled += 1;
+                    Grade::Good
+                } else {
+                    Grade::Again
+                };
+                *scheduled = model_fsrs.next_card(Some(*scheduled), now, grade);
+                *actual = true_fsrs.next_card(Some(*actual), now, grade);
+                reviews += 1;
+            }
+        }
+        daily_reviews.push(reviews);
+    }
+
+    let total: usize = daily_reviews.iter().sum();
+    StudentResult {
+        achieved_retention: recalled as f64 / total as f64,
+        daily_reviews,
+    }
+}
+
+impl Parameters {
+    /// Retention from 0.70 to 0.97 that minimizes the study time per card
+    /// remembered after the given number of days
+    ///
+    /// At each candidate retention, a simulated student learns five new cards
+    /// a day and reviews those that are due, with recall decided by the
+    /// forgetting curve. Learning a card costs three times as much as
+    /// recalling one, and forgetting costs seven times as much to account for
+    /// relearning and the frustration of forgetting. The simulation uses a
+    /// fixed seed, so the result is deterministic.
+    ///
+    /// Horizons of zero days or less have nothing to simulate and return the
+    /// desired retention. Horizons are limited to
+    /// [Fsrs::MAX_INTERVAL_DAYS], though the simulation takes time that
+    /// grows with the square of the horizon.
+    pub fn optimal_retention(&self, horizon_days: i64) -> f64 {
+        const NEW_CARDS_PER_DAY: usize = 5;
+        const LEARN_COST: f64 = 3.0;
+        const RECALL_COST: f64 = 1.0;
+        const FORGET_COST: f64 = 7.0;
+        let Some(horizon_days) = optimization_horizon(horizon_days) else {
+            return self.retention;
+        };
+        let start = Time::default();
+        let end = start + Duration::days(horizon_days);
+
+        (70..=97)
+            .map(|percent| {
+                let retention = percent as f64 / 100.0;
+                let parameters = Self {
+                    retention,
+                    enable_fuzz: false,
+                    ..*self
+                };
+                let fsrs = Fsrs::new(parameters);
+                // Cards learned later are not due until after they are learned
+                let deck: Vec<_> = (0..horizon_days)
+                    .flat_map(|day| {
+                        let learned =
+                            fsrs.next_card(None, start + Duration::days(day), Grade::Good);
+                        std::iter::repeat_n(learned, NEW_CARDS_PER_DAY)
+                    })
+                    .collect();
+                let mut rng = Rng::new(0x5eed);
+                let mut cost = deck.len() as f64 * LEARN_COST;
+                let result = simulate(
+                    parameters,
+                    deck,
+                    start,
+                    horizon_days as usize,
+                    |card, now| {
+                        if rng.next_f64() < card.retrievability(&parameters, now) {
+                            cost += RECALL_COST;
+                            Grade::Good
+                        } else {
+                            cost += FORGET_COST;
+                            Grade::Again
+                        }
+                    },
+                );
+
+                let remembered: f64 = result
+                    .cards
+                    .iter()
+                    .map(|card| card.retrievability(&parameters, end))
+                    .sum();
+                (retention, cost / remembered)
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map_or(self.retention, |(retention, _)| retention)
+    }
+}
+
+/// The number of days to simulate for [Parameters::optimal_retention], or
+/// None if there are none
+fn optimization_horizon(horizon_days: i64) -> Option<i64> {
+    (horizon_days > 0).then(|| horizon_days.min(Fsrs::MAX_INTERVAL_DAYS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{optimization_horizon, simulate, simulate_student};
+    use crate::{
+        fsrs::tests::string_to_utc, weights, Duration, Fsrs, Grade, Parameters, ParametersBuilder,
+        Time,
+    };
+
+    #[test]
+    fn always_recalled() {
+        let start = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let fsrs = Fsrs::default();
+        let deck: Vec<_> = Grade::all()
+            .into_iter()
+            .cycle()
+            .take(40)
+            .map(|grade| fsrs.next_card(None, start, grade))
+            .collect();
+
+        let result = simulate(Parameters::default(), deck.clone(), start, 360, |_, _| {
+            Grade::Good
+        });
+        assert_eq!(result.daily_reviews.len(), 360);
+        assert_eq!(result.memory_states().len(), deck.len());
+
+        let first_month: usize = result.daily_reviews[..30].iter().sum();
+        let last_month: usize = result.daily_reviews[330..].iter().sum();
+        assert!(last_month < first_month);
+        for (before, after) in deck.iter().zip(result.cards.iter()) {
+            assert!(after.interval > before.interval);
+            assert!(after.stability > before.stability);
+        }
+    }
+
+    #[test]
+    fn student() {
+        let start = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let parameters = Parameters::default();
+        let fsrs = Fsrs::new(parameters);
+        let deck = vec![fsrs.next_card(None, start, Grade::Good); 200];
+
+        let matched = simulate_student(parameters, parameters, deck.clone(), start, 365, 1);
+        assert!((matched.achieved_retention - 0.9).abs() < 0.03);
+        assert_eq!(
+            simulate_student(parameters, parameters, deck.clone(), start, 365, 1),
+            matched
+        );
+
+        // A student who forgets faster than the model expects falls short
+        let mut w = Parameters::DEFAULT_WEIGHTS;
+        w[weights::RECALL_STABILITY] -= 1.0;
+        let weaker = ParametersBuilder::new().weights(w).build();
+        let result = simulate_student(weaker, parameters, deck, start, 365, 1);
+        assert!(result.achieved_retention < matched.achieved_retention - 0.05);
+    }
+
+    #[test]
+    fn optimal_retention() {
+        let parameters = Parameters::default();
+        let retention = parameters.optimal_retention(180);
+        assert!((0.7..=0.97).contains(&retention));
+        assert_eq!(parameters.optimal_retention(180), retention);
+
+        let relaxed = ParametersBuilder::new().retention(0.85).build();
+        assert_eq!(relaxed.optimal_retention(0), 0.85);
+        assert_eq!(relaxed.optimal_retention(-30), 0.85);
+        assert_eq!(optimization_horizon(0), None);
+        assert_eq!(optimization_horizon(365), Some(365));
+        assert_eq!(
+            optimization_horizon(i64::MAX),
+            Some(Fsrs::MAX_INTERVAL_DAYS)
+        );
+        assert!(Time::default()
+            .checked_add_signed(Duration::days(Fsrs::MAX_INTERVAL_DAYS))
+            .is_some());
+
+        let mut w = Parameters::DEFAULT_WEIGHTS;
+        for stability in &mut w[..4] {
+            *stability *= 10.0;
+        }
+        let slow = ParametersBuilder::new().weights(w).build();
+        let slow_retention = slow.optimal_retention(180);
+        assert!((0.7..=0.97).contains(&slow_retention));
+        assert!(slow_retention < retention);
+    }
+}
@@ -0,0 +1,143 @@
+//! Summaries of a collection of cards
+
+use crate::{Card, Duration, Fsrs, Grade, Parameters, Time};
+
+/// Aggregate statistics for a collection, from [deck_stats]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct DeckStats {
+    /// Cards that have never been reviewed
+    pub new: usize,
+    /// Cards that were forgotten at their last review
+    pub relearning: usize,
+    /// Other reviewed cards
+    pub review: usize,
+    /// Mean stability of the reviewed cards, or NaN if there are none
+    pub mean_stability: f64,
+    /// Median stability of the reviewed cards, or NaN if there are none
+    pub median_stability: f64,
+    /// Mean difficulty of the reviewed cards, or NaN if there are none
+    pub mean_difficulty: f64,
+    /// Reviewed cards that are due at the given time
+    pub due: usize,
+    /// Reviewed cards predicted to fall to the desired retention on each of
+    /// the next seven study days, where the first day also counts cards that
+    /// already have
+    pub upcoming: [usize; 7],
+}
+
+/// Summarize the cards of a collection at the given time
+///
+/// Cards are classified like [build_queue](crate::build_queue), with None
+/// for cards that have never been reviewed. The upcoming reviews use
+/// [Card::days_to_retrievability] with the card's desired retention rather
+/// than the scheduled due date, so fuzz and the minimum and maximum intervals
+/// are not taken into account. Study days begin at the day cutoff if there is
+/// one and at midnight UTC otherwise.
+pub fn deck_stats(cards: &[Option<Card>], parameters: &Parameters, now: Time) -> DeckStats {
+    let mut stats = DeckStats::default();
+    let mut stabilities = Vec::with_capacity(cards.len());
+    let mut total_difficulty = 0.0;
+    for card in cards {
+        let Some(card) = card else {
+            stats.new += 1;
+            continue;
+        };
+        if card.grade == Grade::Again {
+            stats.relearning += 1;
+        } else {
+            stats.review += 1;
+        }
+        stabilities.push(card.stability);
+        total_difficulty += card.difficulty;
+        if card.is_due(now) {
+            stats.due += 1;
+        }
+
+        let retention = card.retention.unwrap_or(parameters.retention);
+        let days = card.days_to_retrievability(parameters, retention);
+        if days.is_nan() || days >= Fsrs::MAX_INTERVAL_DAYS as f64 {
+            continue;
+        }
+        let predicted = card.reviewed_at + Duration::seconds((days * 86_400.0) as i64);
+        let day = parameters.study_days_between(now, predicted).max(0);
+        if let Some(upcoming) = stats.upcoming.get_mut(day as usize) {
+            *upcoming += 1;
+        }
+    }
+
+    let reviewed = stabilities.len() as f64;
+    stats.mean_stability = stabilities.iter().sum::<f64>() / reviewed;
+    stats.mean_difficulty = total_difficulty / reviewed;
+    stabilities.sort_by(f64::total_cmp);
+    let middle = stabilities.len() / 2;
+    stats.median_stability = match stabilities.len() {
+        0 => f64::NAN,
+        len if len % 2 == 0 => (stabilities[middle - 1] + stabilities[middle]) / 2.0,
+        _ => stabilities[middle],
+    };
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::deck_stats;
+    use crate::{fsrs::tests::string_to_utc, Card, Duration, Grade, Parameters, ParametersBuilder};
+    use chrono::FixedOffset;
+
+    #[test]
+    fn deck() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        // Reviewed so that retrievability falls to 90% when the card is due
+        let card = |stability: f64, due_in_hours: i64, grade| {
+            let interval = Duration::days(stability as i64);
+            Some(Card {
+                grade,
+                interval,
+                ..Card::from_memory_state(
+                    stability,
+                    stability / 10.0 + 1.0,
+                    now + Duration::hours(due_in_hours) - interval,
+                )
+            })
+        };
+        let cards = [
+            None,
+            card(1.0, -30, Grade::Again),
+            card(10.0, 0, Grade::Good),
+            card(20.0, 5, Grade::Good),
+            None,
+            card(40.0, 30, Grade::Easy),
+            card(50.0, 24 * 7, Grade::Hard),
+        ];
+
+        let parameters = Parameters::default();
+        let stats = deck_stats(&cards, &parameters, now);
+        assert_eq!((stats.new, stats.relearning, stats.review), (2, 1, 4));
+        assert_eq!(stats.due, 2);
+        assert_eq!(stats.upcoming, [3, 1, 0, 0, 0, 0, 0]);
+        assert_eq!(stats.mean_stability, 24.2);
+        assert_eq!(stats.median_stability, 20.0);
+        assert!((stats.mean_difficulty - 3.42).abs() < 1e-9);
+
+        // Study days starting at 18:00 put the card due in 30 hours two days
+        // ahead
+        let cutoff = ParametersBuilder::new()
+            .day_cutoff(18, FixedOffset::east_opt(0).unwrap())
+            .build();
+        let stats = deck_stats(&cards, &cutoff, now);
+        assert_eq!(stats.upcoming, [3, 0, 1, 0, 0, 0, 0]);
+
+        // A lower desired retention pushes the predicted review back
+        let mut relaxed = cards;
+        if let Some(card) = &mut relaxed[3] {
+            card.set_retention(Some(0.8)).unwrap();
+        }
+        let stats = deck_stats(&relaxed, &parameters, now);
+        assert_eq!(stats.upcoming, [2, 1, 0, 0, 0, 0, 0]);
+
+        let empty = deck_stats(&[None], &parameters, now);
+        assert_eq!(empty.new, 1);
+        assert!(empty.mean_stability.is_nan());
+        assert!(empty.median_stability.is_nan());
+    }
+}
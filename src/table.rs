@@ -0,0 +1,80 @@
+use crate::{Grade, Parameters};
+
+/// Intervals after a review of a card with a given memory state, from
+/// [Parameters::interval_table]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct IntervalRow {
+    /// Stability before the review
+    pub stability: f64,
+    /// Difficulty before the review
+    pub difficulty: f64,
+    /// Whole days until the next review after grading Hard
+    pub hard: i64,
+    /// Whole days until the next review after grading Good
+    pub good: i64,
+    /// Whole days until the next review after grading Easy
+    pub easy: i64,
+}
+
+impl Parameters {
+    /// Intervals after reviewing on the due date for each combination of
+    /// stability and difficulty
+    ///
+    /// Rows are ordered by stability and then by difficulty. The review
+    /// happens after the interval these parameters give for the stability,
+    /// and the intervals are before fuzz.
+    pub fn interval_table(&self, stabilities: &[f64], difficulties: &[f64]) -> Vec<IntervalRow> {
+        let mut rows = Vec::with_capacity(stabilities.len() * difficulties.len());
+        for &stability in stabilities {
+            let elapsed_days = self.next_interval(stability);
+            let retrievability = self.forgetting_curve(elapsed_days, stability);
+            for &difficulty in difficulties {
+                let interval = |grade| {
+                    let next = self.next_stability(difficulty, stability, retrievability, grade);
+                    self.graded_interval(next.max(self.minimum_stability), grade) as i64
+                };
+                rows.push(IntervalRow {
+                    stability,
+                    difficulty,
+                    hard: interval(Grade::Hard),
+                    good: interval(Grade::Good),
+                    easy: interval(Grade::Easy),
+                });
+            }
+        }
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fsrs::tests::string_to_utc, Card, Duration, Fsrs, Grade, Parameters};
+
+    #[test]
+    fn interval_table() {
+        let parameters = Parameters::default();
+        let stabilities = [1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0];
+        let difficulties = [1.0, 5.0, 10.0];
+        let table = parameters.interval_table(&stabilities, &difficulties);
+        assert_eq!(table.len(), stabilities.len() * difficulties.len());
+
+        for (i, row) in table.iter().enumerate() {
+            assert_eq!(row.stability, stabilities[i / difficulties.len()]);
+            assert_eq!(row.difficulty, difficulties[i % difficulties.len()]);
+            assert!(row.hard <= row.good && row.good <= row.easy);
+            if let Some(next) = table.get(i + difficulties.len()) {
+                assert!(next.hard >= row.hard);
+                assert!(next.good >= row.good);
+                assert!(next.easy >= row.easy);
+            }
+        }
+
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = Card {
+            interval: Duration::days(10),
+            ..Card::from_memory_state(10.0, 5.0, now)
+        };
+        let next = Fsrs::new(parameters).next_card(Some(card), card.due(), Grade::Good);
+        assert_eq!(table[3 * 3 + 1].good, next.interval.num_days());
+    }
+}
@@ -0,0 +1,235 @@
+//! JavaScript bindings for use with `wasm-pack`
+//!
+//! The crate in `examples/wasm` packages these bindings with
+//! `wasm-pack build examples/wasm`.
+//!
+//! Times cross the boundary as milliseconds since the Unix epoch, the same as
+//! JavaScript's `Date.now()`. Cards are passed as plain objects in the shape
+//! of [JsCard], with `null` or `undefined` standing for a new card.
+//!
+//! ```js
+//! import { Scheduler } from "fsrsrs";
+//!
+//! const scheduler = new Scheduler(new Float64Array(weights), 0.9);
+//! const card = scheduler.schedule(null, Date.now(), 3);
+//! const { again, hard, good, easy } = scheduler.preview(card, Date.now());
+//! ```
+
+use crate::{Card, Cards, Fsrs, Grade, Parameters, Time};
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+use wasm_bindgen::prelude::*;
+
+/// A card in the shape it takes as a JavaScript object
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsCard {
+    /// Milliseconds since the epoch of the last review
+    pub reviewed_at: f64,
+    /// Milliseconds since the epoch when the card is next due
+    pub due: f64,
+    /// The grade of the last review
+    pub grade: Grade,
+    /// Days for retrievability to fall to 90%
+    pub stability: f64,
+    /// How hard the card is to remember, from 1 to 10
+    pub difficulty: f64,
+    /// Number of times the card was forgotten
+    #[serde(default)]
+    pub lapses: u32,
+    /// Desired retention for this card in place of the scheduler's
+    #[serde(default)]
+    pub retention: Option<f64>,
+}
+
+impl From<Card> for JsCard {
+    fn from(card: Card) -> Self {
+        Self {
+            reviewed_at: to_millis(card.reviewed_at),
+            due: to_millis(card.due()),
+            grade: card.grade,
+            stability: card.stability,
+            difficulty: card.difficulty,
+            lapses: card.lapses,
+            retention: card.retention,
+        }
+    }
+}
+
+impl TryFrom<JsCard> for Card {
+    type Error = TimeRangeError;
+
+    fn try_from(card: JsCard) -> Result<Self, Self::Error> {
+        let reviewed_at = from_millis(card.reviewed_at)?;
+        let due = from_millis(card.due)?;
+        Ok(Card {
+            reviewed_at,
+            interval: due - reviewed_at,
+            grade: card.grade,
+            stability: card.stability,
+            difficulty: card.difficulty,
+            lapses: card.lapses,
+            retention: card.retention,
+        })
+    }
+}
+
+/// Milliseconds since the epoch that are not finite or are outside the
+/// range of [Time]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeRangeError(pub f64);
+
+impl Display for TimeRangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "timestamp {} is out of range", self.0)
+    }
+}
+
+impl Error for TimeRangeError {}
+
+/// Convert a time to milliseconds since the epoch
+pub fn to_millis(time: Time) -> f64 {
+    time.timestamp_millis() as f64
+}
+
+/// Convert milliseconds since the epoch to a time, dropping any fraction of
+/// a millisecond
+pub fn from_millis(millis: f64) -> Result<Time, TimeRangeError> {
+    if !millis.is_finite() || millis.abs() > i64::MAX as f64 {
+        return Err(TimeRangeError(millis));
+    }
+    Utc.timestamp_millis_opt(millis as i64)
+        .single()
+        .ok_or(TimeRangeError(millis))
+}
+
+/// A scheduler that can be called from JavaScript
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Scheduler {
+    fsrs: Fsrs,
+}
+
+#[wasm_bindgen]
+impl Scheduler {
+    /// Create a scheduler from 17, 19, or 21 weights and the desired
+    /// retention
+    #[wasm_bindgen(constructor)]
+    pub fn new(weights: &[f64], retention: f64) -> Result<Scheduler, JsError> {
+        let parameters = Parameters {
+            retention,
+            ..Parameters::from_weights(weights)?
+        };
+        if let Err(errors) = parameters.validate() {
+            let messages: Vec<_> = errors.iter().map(ToString::to_string).collect();
+            return Err(JsError::new(&messages.join("; ")));
+        }
+        Ok(Self {
+            fsrs: Fsrs::new(parameters),
+        })
+    }
+
+    /// Schedule a card reviewed at `now` with a grade from 1 for Again to 4
+    /// for Easy, where a `null` card is new
+    pub fn schedule(&self, card: JsValue, now: f64, grade: u8) -> Result<JsValue, JsError> {
+        let card: Option<JsCard> = serde_wasm_bindgen::from_value(card)?;
+        let next = self.schedule_card(card, now, Grade::try_from(grade)?)?;
+        Ok(serde_wasm_bindgen::to_value(&next)?)
+    }
+
+    /// The card that would result from each grade of a review at `now`, as
+    /// an object with `again`, `hard`, `good`, and `easy` fields
+    pub fn preview(&self, card: JsValue, now: f64) -> Result<JsValue, JsError> {
+        let card: Option<JsCard> = serde_wasm_bindgen::from_value(card)?;
+        let previews = self.preview_card(card, now)?;
+        Ok(serde_wasm_bindgen::to_value(&previews)?)
+    }
+}
+
+impl Scheduler {
+    /// The scheduling behind [Scheduler::schedule], without the conversion
+    /// to and from JavaScript values
+    pub fn schedule_card(
+        &self,
+        card: Option<JsCard>,
+        now: f64,
+        grade: Grade,
+    ) -> Result<JsCard, TimeRangeError> {
+        let card = card.map(Card::try_from).transpose()?;
+        let next = self.fsrs.next_card(card, from_millis(now)?, grade);
+        Ok(next.into())
+    }
+
+    /// The scheduling behind [Scheduler::preview], without the conversion
+    /// to and from JavaScript values
+    pub fn preview_card(
+        &self,
+        card: Option<JsCard>,
+        now: f64,
+    ) -> Result<Cards<JsCard>, TimeRangeError> {
+        let card = card.map(Card::try_from).transpose()?;
+        let previews = self.fsrs.preview(card, from_millis(now)?);
+        Ok(previews.map(|(_, card)| card.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_millis, to_millis, JsCard, Scheduler, TimeRangeError};
+    use crate::{fsrs::tests::string_to_utc, Card, Fsrs, Grade, Parameters};
+
+    #[test]
+    fn millis() {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        assert_eq!(to_millis(now), 1_669_725_000_000.0);
+        assert_eq!(from_millis(1_669_725_000_000.0), Ok(now));
+        assert!(from_millis(f64::NAN).is_err());
+        assert!(from_millis(f64::INFINITY).is_err());
+        assert!(from_millis(1e300).is_err());
+    }
+
+    #[test]
+    fn card_round_trip() -> Result<(), TimeRangeError> {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let card = Fsrs::default().next_card(None, now, Grade::Good);
+        let js = JsCard::from(card);
+        assert_eq!(js.reviewed_at, to_millis(now));
+        assert_eq!(js.due, to_millis(card.due()));
+        assert_eq!(Card::try_from(js)?, card);
+
+        let json = serde_json::to_value(js).unwrap();
+        assert_eq!(json["reviewedAt"], to_millis(now));
+        assert_eq!(json["grade"], "good");
+        let parsed: JsCard = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, js);
+        Ok(())
+    }
+
+    #[test]
+    fn scheduler() -> Result<(), TimeRangeError> {
+        let now = string_to_utc("2022-11-29 12:30:00 +0000 UTC");
+        let Ok(scheduler) = Scheduler::new(&Parameters::DEFAULT_WEIGHTS, 0.9) else {
+            panic!("default weights should be valid");
+        };
+        let fsrs = Fsrs::default();
+
+        let first = scheduler.schedule_card(None, to_millis(now), Grade::Good)?;
+        let card = fsrs.next_card(None, now, Grade::Good);
+        assert_eq!(first, JsCard::from(card));
+
+        let later = card.due();
+        let previews = scheduler.preview_card(Some(first), to_millis(later))?;
+        assert_eq!(
+            previews,
+            fsrs.preview(Some(card), later).map(|(_, card)| card.into())
+        );
+        assert!(scheduler
+            .schedule_card(Some(first), f64::NAN, Grade::Good)
+            .is_err());
+        Ok(())
+    }
+}
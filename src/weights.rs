@@ -0,0 +1,102 @@
+//! Positions of the FSRS weights
+//!
+//! Use these with [Parameters::weights](crate::Parameters::weights) or
+//! when building a weight array rather than remembering what each index
+//! means. FSRS-5 uses the first 19 weights and FSRS-6 uses all 21.
+
+/// Initial stability in days after a first review with each grade, indexed
+/// by [Grade::index](crate::Grade::index)
+pub const INITIAL_STABILITY: [usize; 4] = [0, 1, 2, 3];
+
+/// Initial difficulty after a first review graded Again
+pub const INITIAL_DIFFICULTY: usize = 4;
+
+/// How quickly initial difficulty falls with easier first grades
+pub const INITIAL_DIFFICULTY_GRADE: usize = 5;
+
+/// Change in difficulty for each grade step away from Good
+pub const DIFFICULTY_DELTA: usize = 6;
+
+/// Portion of difficulty pulled back towards its initial value for Easy
+/// after each review
+pub const MEAN_REVERSION: usize = 7;
+
+/// Overall scale of stability growth after a successful review
+pub const RECALL_STABILITY: usize = 8;
+
+/// How much higher stability slows further growth
+pub const RECALL_STABILITY_DECAY: usize = 9;
+
+/// How much lower retrievability boosts stability growth
+pub const RECALL_RETRIEVABILITY: usize = 10;
+
+/// Overall scale of stability after a lapse
+pub const FORGET_STABILITY: usize = 11;
+
+/// How much higher difficulty lowers stability after a lapse
+pub const FORGET_DIFFICULTY: usize = 12;
+
+/// How much of the previous stability survives a lapse
+pub const FORGET_STABILITY_EXPONENT: usize = 13;
+
+/// How much lower retrievability raises stability after a lapse
+pub const FORGET_RETRIEVABILITY: usize = 14;
+
+/// Multiplier on stability growth for Hard
+pub const HARD_PENALTY: usize = 15;
+
+/// Multiplier on stability growth for Easy
+pub const EASY_BONUS: usize = 16;
+
+/// Scale of stability change for reviews on the same day
+pub const SHORT_TERM_STABILITY: usize = 17;
+
+/// Offset by grade of stability change for reviews on the same day
+pub const SHORT_TERM_GRADE: usize = 18;
+
+/// How much higher stability damps same-day stability change, in FSRS-6
+pub const SHORT_TERM_STABILITY_DECAY: usize = 19;
+
+/// Forgetting curve decay, in FSRS-6
+pub const DECAY: usize = 20;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Fsrs, Grade, Parameters, ParametersBuilder, Version};
+
+    #[test]
+    fn default_schedules() {
+        let parameters = Parameters::default();
+        let w = parameters.weights();
+        assert_eq!(w.len(), Version::Fsrs5.weight_count());
+        for grade in Grade::all() {
+            let stability = w[INITIAL_STABILITY[grade.index() as usize]];
+            assert_eq!(parameters.init_stability(grade), stability);
+        }
+        assert_eq!(
+            parameters.init_difficulty(Grade::Again),
+            w[INITIAL_DIFFICULTY]
+        );
+        assert!(SHORT_TERM_GRADE < Version::Fsrs5.weight_count());
+        assert!(DECAY < Version::Fsrs6.weight_count());
+
+        // Changing a weight by name changes the matching part of the model
+        let mut changed = Parameters::DEFAULT_WEIGHTS;
+        changed[EASY_BONUS] *= 2.0;
+        let changed = Fsrs::new(ParametersBuilder::new().weights(changed).build());
+        let fsrs = Fsrs::default();
+        let card = fsrs.next_card(None, crate::Time::default(), Grade::Good);
+        let later = card.due();
+        for grade in [Grade::Again, Grade::Hard, Grade::Good] {
+            assert_eq!(
+                changed.next_card(Some(card), later, grade),
+                fsrs.next_card(Some(card), later, grade)
+            );
+        }
+        assert!(
+            changed.next_card(Some(card), later, Grade::Easy).stability
+                > fsrs.next_card(Some(card), later, Grade::Easy).stability
+        );
+    }
+}